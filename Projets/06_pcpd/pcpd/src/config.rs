@@ -0,0 +1,153 @@
+use std::fs;
+use std::str::FromStr;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::PcpdError;
+
+// une clé inconnue (mal orthographiée) est une erreur plutôt qu'un paramètre ignoré
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PricingConfig {
+    #[serde(rename = "model type")]
+    pub model_type: String, // type de modèle ("bs")
+    #[serde(rename = "option type")]
+    pub option_type: String, // type d'option ("basket", "asian", ...)
+    #[serde(rename = "option size")]
+    pub option_size: usize, // nombre d'actifs d
+    #[serde(default)]
+    pub strike: Option<f64>, // strike (absent pour l'option performance)
+    pub spot: Vec<f64>,       // valeurs initiales des sous-jacents
+    pub maturity: f64,        // maturité T
+    pub volatility: Vec<f64>, // vecteur de volatilités
+    #[serde(rename = "interest rate")]
+    pub interest_rate: f64, // taux d'intérêt
+    #[serde(rename = "dividend rate", default)]
+    pub dividend_rate: Vec<f64>, // taux de dividende continu par actif (nuls si absent)
+    pub correlation: f64,     // paramètre de corrélation
+    #[serde(default)]
+    pub trend: Vec<f64>, // tendance sous la probabilité historique
+    #[serde(default)]
+    pub kappa: Vec<f64>, // vitesse de retour à la moyenne de la variance (Heston)
+    #[serde(default)]
+    pub theta: Vec<f64>, // variance de long terme (Heston)
+    #[serde(default)]
+    pub xi: Vec<f64>, // volatilité de la variance (Heston)
+    #[serde(default)]
+    pub v0: Vec<f64>, // variance initiale (Heston, volatility^2 si absent)
+    #[serde(rename = "spot vol correlation", default)]
+    pub spot_vol_correlation: Vec<f64>, // corrélation entre actif et variance (Heston, nulle si absent)
+    #[serde(rename = "euler steps", default)]
+    pub euler_steps: Option<usize>, // nombre de pas du schéma d'Euler sur [0, T]
+    #[serde(rename = "jump intensity", default)]
+    pub jump_intensity: Vec<f64>, // intensité des sauts par actif (Merton)
+    #[serde(rename = "jump mean", default)]
+    pub jump_mean: Vec<f64>, // moyenne du log des sauts (Merton)
+    #[serde(rename = "jump volatility", default)]
+    pub jump_volatility: Vec<f64>, // écart-type du log des sauts (Merton)
+    #[serde(rename = "common jumps", default)]
+    pub common_jumps: bool, // un même processus de Poisson pour tous les actifs (Merton)
+    #[serde(default)]
+    pub barrier: Option<f64>, // niveau de la barrière (sur le niveau du panier)
+    #[serde(rename = "barrier type", default)]
+    pub barrier_type: Option<String>, // "up-and-out", "up-and-in", "down-and-out" ou "down-and-in"
+    #[serde(rename = "barrier payoff", default)]
+    pub barrier_payoff: Option<String>, // payoff vanille à maturité, "call" (défaut) ou "put"
+    #[serde(default)]
+    pub rebate: f64, // montant payé en T si l'option est désactivée (ou jamais activée)
+    #[serde(rename = "barrier correction", default)]
+    pub barrier_correction: Option<String>, // "none" (défaut), "shift" ou "bridge" (modèle bs)
+    #[serde(rename = "payoff coefficients", default)]
+    pub payoff_coefficients: Vec<f64>, // pondérations du panier
+    #[serde(rename = "fixing dates number")]
+    pub fixing_dates_number: usize, // nombre de dates de fixing N
+    #[serde(rename = "sample number")]
+    pub sample_number: usize, // nombre de tirages M
+    #[serde(rename = "hedging dates number")]
+    pub hedging_dates_number: usize, // nombre de dates de rebalancement H
+    #[serde(rename = "fd step")]
+    pub fd_step: f64, // pas h des différences finies
+}
+
+impl PricingConfig {
+    pub fn from_json(json: &Value) -> Result<Self, PcpdError> {
+        let config: PricingConfig = serde_json::from_value(json.clone())?;
+        config.expanded()
+    }
+
+    pub fn from_file(path: &str) -> Result<Self, PcpdError> {
+        let data = fs::read_to_string(path).map_err(|source| PcpdError::Io {
+            path: path.to_string(),
+            source,
+        })?;
+        data.parse()
+    }
+
+    // un vecteur à un seul élément vaut pour tous les actifs : "spot": [100.0] => [100.0; d]
+    fn expanded(mut self) -> Result<Self, PcpdError> {
+        let d = self.option_size;
+        if d == 0 {
+            return Err(PcpdError::Config(
+                "\"option size\" must be positive".to_string(),
+            ));
+        }
+        if self.fixing_dates_number == 0 {
+            return Err(PcpdError::Config(
+                "\"fixing dates number\" must be positive".to_string(),
+            ));
+        }
+
+        expand("spot", &mut self.spot, d, true)?;
+        expand("volatility", &mut self.volatility, d, true)?;
+        expand("trend", &mut self.trend, d, false)?;
+        expand("dividend rate", &mut self.dividend_rate, d, false)?;
+        expand("kappa", &mut self.kappa, d, false)?;
+        expand("theta", &mut self.theta, d, false)?;
+        expand("xi", &mut self.xi, d, false)?;
+        expand("v0", &mut self.v0, d, false)?;
+        expand("jump intensity", &mut self.jump_intensity, d, false)?;
+        expand("jump mean", &mut self.jump_mean, d, false)?;
+        expand("jump volatility", &mut self.jump_volatility, d, false)?;
+        expand(
+            "spot vol correlation",
+            &mut self.spot_vol_correlation,
+            d,
+            false,
+        )?;
+        expand(
+            "payoff coefficients",
+            &mut self.payoff_coefficients,
+            d,
+            false,
+        )?;
+
+        Ok(self)
+    }
+}
+
+impl FromStr for PricingConfig {
+    type Err = PcpdError;
+
+    fn from_str(data: &str) -> Result<Self, Self::Err> {
+        let config: PricingConfig = serde_json::from_str(data)?;
+        config.expanded()
+    }
+}
+
+fn expand(name: &str, v: &mut Vec<f64>, d: usize, required: bool) -> Result<(), PcpdError> {
+    if v.len() == 1 && d > 1 {
+        *v = vec![v[0]; d];
+    }
+
+    if v.len() != d && (required || !v.is_empty()) {
+        return Err(PcpdError::Config(format!(
+            "\"{}\" has {} values but \"option size\" is {}",
+            name,
+            v.len(),
+            d
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use thiserror::Error;
+
+use crate::math::linalg::LinalgError;
+
+#[derive(Debug, Error)]
+pub enum PcpdError {
+    #[error("cannot read {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("invalid configuration: {0}")]
+    Config(String),
+
+    #[error(
+        "unknown option type \"{0}\" (expected basket, asian, performance, call, put, digital or barrier)"
+    )]
+    UnknownOptionType(String),
+
+    #[error("unknown model type \"{0}\" (expected bs, heston or merton)")]
+    UnknownModelType(String),
+
+    #[error("invalid market data: {0}")]
+    Market(String),
+
+    #[error(transparent)]
+    Linalg(#[from] LinalgError),
+
+    #[error("invalid correlation {correlation} for {model_size} assets: {source}")]
+    InvalidCorrelation {
+        correlation: f64,
+        model_size: usize,
+        #[source]
+        source: LinalgError,
+    },
+
+    #[error("invalid model: {0}")]
+    Model(String),
+
+    #[error("invalid payoff: {0}")]
+    Payoff(String),
+}
+
+impl From<serde_json::Error> for PcpdError {
+    fn from(e: serde_json::Error) -> Self {
+        PcpdError::Config(e.to_string())
+    }
+}
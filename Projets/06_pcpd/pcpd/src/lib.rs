@@ -1,3 +1,6 @@
+pub mod config;
+pub mod error;
+pub mod market;
 pub mod math;
 pub mod mc;
 pub mod model;
@@ -1,27 +1,187 @@
-use ndarray::Array2;
-use ndarray::array;
-use pcpd::options::call::CallOption;
-use pcpd::options::option::Option;
-use pcpd::options::put::PutOption;
+use std::fs;
+
+use clap::{Parser, Subcommand};
+use ndarray::Array1;
+use rand::SeedableRng;
+use serde::Serialize;
+
+use pcpd::config::PricingConfig;
+use pcpd::error::PcpdError;
+use pcpd::market::MarketData;
+use pcpd::mc::control::GeometricControl;
+use pcpd::mc::greeks::GreeksMethod;
+use pcpd::mc::hedging::Hedger;
+use pcpd::mc::pricer::MonteCarlo;
+use pcpd::mc::rng::Xoshiro256PlusPlus;
+use pcpd::model::black_scholes::{BlackScholesModel, PathConstruction};
+use pcpd::options::factory::option_from_config;
+
+#[derive(Parser)]
+#[command(
+    name = "pcpd",
+    about = "Monte Carlo pricing and delta hedging of basket, asian and performance options"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Override the "sample number" of the configuration
+    #[arg(long, global = true)]
+    samples: Option<usize>,
+
+    /// Seed of the random number generator (random if omitted)
+    #[arg(long, global = true)]
+    seed: Option<u64>,
+
+    /// Simulate the paths in antithetic pairs
+    #[arg(long, global = true)]
+    antithetic: bool,
+
+    /// Use the closed-form geometric asian or basket option as a control variate
+    #[arg(long, global = true)]
+    control: bool,
+
+    /// Number of worker threads
+    #[arg(long, global = true)]
+    threads: Option<usize>,
+
+    /// Write the JSON result to this file instead of stdout
+    #[arg(short, long, global = true)]
+    output: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Price and deltas at t = 0
+    Price {
+        /// Configuration file (e.g. data/call/call.json)
+        config: String,
+        /// Use randomized quasi-Monte Carlo (Sobol points, Brownian bridge) with this many
+        /// independently shifted replications
+        #[arg(long, value_name = "REPLICATIONS")]
+        qmc: Option<usize>,
+        /// Also estimate delta, gamma and vega without finite differences
+        /// ("pathwise" or "likelihood-ratio")
+        #[arg(long, value_name = "METHOD")]
+        greeks: Option<GreeksMethod>,
+    },
+    /// Delta hedging along a market path and final P&L
+    Hedge {
+        /// Configuration file (e.g. data/call/call.json)
+        config: String,
+        /// Market path (e.g. data/call/call_market.txt)
+        market: String,
+    },
+    /// Simulate a market path under the historical "trend" drift, in the *_market.txt format
+    Market {
+        /// Configuration file (e.g. data/call/call.json)
+        config: String,
+    },
+}
 
 fn main() {
-    let path: Array2<f64> = array![[90.0], [100.0]];
+    let cli = Cli::parse();
+
+    if let Err(e) = run(&cli) {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(cli: &Cli) -> Result<(), PcpdError> {
+    if let Some(threads) = cli.threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .map_err(|e| PcpdError::Config(format!("cannot start {} threads: {}", threads, e)))?;
+    }
+
+    let mut rng = match cli.seed {
+        Some(seed) => Xoshiro256PlusPlus::seed_from_u64(seed),
+        None => Xoshiro256PlusPlus::from_entropy(),
+    };
 
-    let strike = 90.;
+    match &cli.command {
+        Command::Price {
+            config,
+            qmc,
+            greeks,
+        } => {
+            let config = load_config(config, cli.samples)?;
+            let option = option_from_config(&config)?;
+            let mut pricer = MonteCarlo::from_config(&config, option)?;
+            pricer.antithetic = cli.antithetic;
+            if cli.control {
+                pricer.control = Some(Box::new(GeometricControl::from_config(&config)?));
+            }
 
-    let call = CallOption::new(strike);
+            let mut result = match qmc {
+                Some(replications) => {
+                    pricer
+                        .model
+                        .set_path_construction(PathConstruction::BrownianBridge);
+                    pricer.price_and_delta_rqmc(*replications, &mut rng)?
+                }
+                None => pricer.price_and_delta(&mut rng)?,
+            };
+            if let Some(method) = greeks {
+                result.greeks = Some(pricer.greeks(*method, &mut rng)?);
+            }
+            write_output(&result, cli.output.as_deref())
+        }
+        Command::Hedge { config, market } => {
+            let config = load_config(config, cli.samples)?;
+            let option = option_from_config(&config)?;
+            let mut hedger = Hedger::from_config(&config, option)?;
+            hedger.pricer.antithetic = cli.antithetic;
+            if cli.control {
+                hedger.pricer.control = Some(Box::new(GeometricControl::from_config(&config)?));
+            }
+            let market = MarketData::from_file(market)?;
+            market.check_config(&config)?;
 
-    let put = PutOption::new(strike);
+            let result = hedger.hedge(&market.prices, &mut rng)?;
+            write_output(&result, cli.output.as_deref())
+        }
+        Command::Market { config } => {
+            let config = load_config(config, cli.samples)?;
+            if config.trend.is_empty() {
+                return Err(PcpdError::Config(
+                    "\"trend\" is required to simulate a market path".to_string(),
+                ));
+            }
+            let model = BlackScholesModel::from_config(&config)?;
 
-    let payoff_call = call.payoff(&path);
-    println!(
-        "The payoff of call option with strike {} is {}",
-        strike, payoff_call
-    );
+            let trend = Array1::from(config.trend.clone());
+            let prices = model.simulate_market(&trend, config.hedging_dates_number, &mut rng)?;
+            let market = MarketData::from_model(&model, prices);
+            write_text(&market.to_string(), cli.output.as_deref())
+        }
+    }
+}
+
+fn load_config(path: &str, samples: Option<usize>) -> Result<PricingConfig, PcpdError> {
+    let mut config = PricingConfig::from_file(path)?;
+    if let Some(samples) = samples {
+        config.sample_number = samples;
+    }
+    Ok(config)
+}
+
+fn write_output<T: Serialize>(result: &T, output: Option<&str>) -> Result<(), PcpdError> {
+    let json = serde_json::to_string_pretty(result)?;
+    write_text(&(json + "\n"), output)
+}
 
-    let payoff_put = put.payoff(&path);
-    println!(
-        "The payoff of put option with strike {} is {}",
-        strike, payoff_put
-    );
+fn write_text(data: &str, output: Option<&str>) -> Result<(), PcpdError> {
+    match output {
+        Some(path) => fs::write(path, data).map_err(|source| PcpdError::Io {
+            path: path.to_string(),
+            source,
+        }),
+        None => {
+            print!("{}", data);
+            Ok(())
+        }
+    }
 }
@@ -0,0 +1,279 @@
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+
+use ndarray::{Array1, Array2};
+
+use crate::config::PricingConfig;
+use crate::error::PcpdError;
+use crate::model::black_scholes::BlackScholesModel;
+
+// en-têtes d'un fichier *_market.txt, dans l'ordre d'écriture
+const HEADERS: [&str; 7] = [
+    "maturity time",
+    "model size",
+    "number of dates",
+    "spot",
+    "dividend rate",
+    "correlation",
+    "volatility",
+];
+
+// tolérance de comparaison avec la configuration (valeurs écrites avec 6 décimales)
+const HEADER_TOLERANCE: f64 = 1e-6;
+
+// trajectoire de marché observée aux dates tau_i = i * T / H, i = 0..H
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketData {
+    pub maturity: f64,              // maturité T
+    pub model_size: usize,          // nombre d'actifs d
+    pub dates_number: usize,        // nombre de dates H après t = 0
+    pub spot: Array1<f64>,          // valeurs initiales des sous-jacents
+    pub dividend_rate: Array1<f64>, // taux de dividende continu par actif
+    pub correlation: f64,           // paramètre de corrélation
+    pub volatility: Array1<f64>,    // vecteur de volatilités
+    pub prices: Array2<f64>,        // (H + 1) x d prix observés
+}
+
+impl MarketData {
+    // en-têtes repris du modèle qui a servi à simuler les prix
+    pub fn from_model(model: &BlackScholesModel, prices: Array2<f64>) -> Self {
+        MarketData {
+            maturity: model.maturity,
+            model_size: model.model_size,
+            dates_number: prices.nrows().saturating_sub(1),
+            spot: model.spots.clone(),
+            dividend_rate: model.dividend_rate.clone(),
+            correlation: model.correlation,
+            volatility: model.volatility.clone(),
+            prices,
+        }
+    }
+
+    pub fn from_file(path: &str) -> Result<Self, PcpdError> {
+        let data = fs::read_to_string(path).map_err(|source| PcpdError::Io {
+            path: path.to_string(),
+            source,
+        })?;
+
+        data.parse().map_err(|e| match e {
+            PcpdError::Market(message) => PcpdError::Market(format!("{}: {}", path, message)),
+            e => e,
+        })
+    }
+
+    pub fn write_file(&self, path: &str) -> Result<(), PcpdError> {
+        fs::write(path, self.to_string()).map_err(|source| PcpdError::Io {
+            path: path.to_string(),
+            source,
+        })
+    }
+
+    // les en-têtes doivent décrire le modèle de la configuration
+    pub fn check_config(&self, config: &PricingConfig) -> Result<(), PcpdError> {
+        let d = config.option_size;
+        let dividend_rate = match config.dividend_rate.is_empty() {
+            true => vec![0.0; d],
+            false => config.dividend_rate.clone(),
+        };
+
+        let checks = [
+            ("maturity time", vec![self.maturity], vec![config.maturity]),
+            ("model size", vec![self.model_size as f64], vec![d as f64]),
+            (
+                "number of dates",
+                vec![self.dates_number as f64],
+                vec![config.hedging_dates_number as f64],
+            ),
+            ("spot", self.spot.to_vec(), config.spot.clone()),
+            ("dividend rate", self.dividend_rate.to_vec(), dividend_rate),
+            (
+                "correlation",
+                vec![self.correlation],
+                vec![config.correlation],
+            ),
+            (
+                "volatility",
+                self.volatility.to_vec(),
+                config.volatility.clone(),
+            ),
+        ];
+
+        for (key, values, expected) in checks {
+            let matches = values.len() == expected.len()
+                && values
+                    .iter()
+                    .zip(&expected)
+                    .all(|(x, y)| (x - y).abs() <= HEADER_TOLERANCE * y.abs().max(1.0));
+            if !matches {
+                return Err(PcpdError::Market(format!(
+                    "header \"{}\" is {:?} but the configuration gives {:?}",
+                    key, values, expected
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for MarketData {
+    type Err = PcpdError;
+
+    fn from_str(data: &str) -> Result<Self, Self::Err> {
+        let error =
+            |line: usize, message: String| PcpdError::Market(format!("line {}: {}", line, message));
+        let numbers = |line: usize, values: &str| {
+            values
+                .split_whitespace()
+                .map(|x| x.parse::<f64>())
+                .collect::<Result<Vec<f64>, _>>()
+                .map_err(|e| error(line, format!("{} in \"{}\"", e, values.trim())))
+        };
+
+        let mut headers: [Option<Vec<f64>>; 7] = Default::default();
+        let mut rows: Vec<(usize, Vec<f64>)> = Vec::new();
+
+        for (index, line) in data.lines().map(str::trim).enumerate() {
+            let line_number = index + 1;
+            if line.is_empty() {
+                continue;
+            }
+
+            // en-tête "# clé: valeurs" ; les autres lignes commentées sont ignorées
+            if let Some(comment) = line.strip_prefix('#') {
+                let Some((key, values)) = comment.split_once(':') else {
+                    continue;
+                };
+                let key = key.trim();
+                let Some(position) = HEADERS.iter().position(|&header| header == key) else {
+                    continue;
+                };
+
+                if !rows.is_empty() {
+                    return Err(error(
+                        line_number,
+                        format!("header \"{}\" after the prices", key),
+                    ));
+                }
+                if headers[position].is_some() {
+                    return Err(error(line_number, format!("duplicated header \"{}\"", key)));
+                }
+                headers[position] = Some(numbers(line_number, values)?);
+                continue;
+            }
+
+            rows.push((line_number, numbers(line_number, line)?));
+        }
+
+        let header = |i: usize| {
+            headers[i]
+                .clone()
+                .ok_or_else(|| PcpdError::Market(format!("missing header \"{}\"", HEADERS[i])))
+        };
+        let scalar = |i: usize| {
+            let values = header(i)?;
+            match values[..] {
+                [value] => Ok(value),
+                _ => Err(PcpdError::Market(format!(
+                    "header \"{}\" has {} values but expects one",
+                    HEADERS[i],
+                    values.len()
+                ))),
+            }
+        };
+        let integer = |i: usize| {
+            let value = scalar(i)?;
+            if value < 0.0 || value.fract() != 0.0 {
+                return Err(PcpdError::Market(format!(
+                    "header \"{}\" is {} but expects a non-negative integer",
+                    HEADERS[i], value
+                )));
+            }
+            Ok(value as usize)
+        };
+
+        let maturity = scalar(0)?;
+        let model_size = integer(1)?;
+        let dates_number = integer(2)?;
+        let correlation = scalar(5)?;
+
+        let vector = |i: usize| {
+            let values = header(i)?;
+            if values.len() != model_size {
+                return Err(PcpdError::Market(format!(
+                    "header \"{}\" has {} values but \"model size\" is {}",
+                    HEADERS[i],
+                    values.len(),
+                    model_size
+                )));
+            }
+            Ok(Array1::from(values))
+        };
+        let spot = vector(3)?;
+        let dividend_rate = vector(4)?;
+        let volatility = vector(6)?;
+
+        // H + 1 lignes de d prix
+        if rows.len() != dates_number + 1 {
+            return Err(PcpdError::Market(format!(
+                "{} price rows but \"number of dates\" is {} (expected {} rows)",
+                rows.len(),
+                dates_number,
+                dates_number + 1
+            )));
+        }
+        for (line_number, row) in &rows {
+            if row.len() != model_size {
+                return Err(error(
+                    *line_number,
+                    format!("{} prices but \"model size\" is {}", row.len(), model_size),
+                ));
+            }
+        }
+
+        let values: Vec<f64> = rows.into_iter().flat_map(|(_, row)| row).collect();
+        let prices = Array2::from_shape_vec((dates_number + 1, model_size), values).unwrap();
+
+        Ok(MarketData {
+            maturity,
+            model_size,
+            dates_number,
+            spot,
+            dividend_rate,
+            correlation,
+            volatility,
+            prices,
+        })
+    }
+}
+
+// même format que les fichiers lus : 6 décimales, une ligne de prix par date
+impl fmt::Display for MarketData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values = |v: &[f64]| {
+            v.iter()
+                .map(|x| format!("{:.6}", x))
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+
+        writeln!(f, "# maturity time: {:.6}", self.maturity)?;
+        writeln!(f, "# model size: {}", self.model_size)?;
+        writeln!(f, "# number of dates: {}", self.dates_number)?;
+        writeln!(f, "# spot: {}", values(&self.spot.to_vec()))?;
+        writeln!(
+            f,
+            "# dividend rate: {}",
+            values(&self.dividend_rate.to_vec())
+        )?;
+        writeln!(f, "# correlation: {:.6}", self.correlation)?;
+        writeln!(f, "# volatility: {}", values(&self.volatility.to_vec()))?;
+
+        for row in self.prices.rows() {
+            writeln!(f, "{}", values(&row.to_vec()))?;
+        }
+
+        Ok(())
+    }
+}
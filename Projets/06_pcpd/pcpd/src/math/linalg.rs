@@ -0,0 +1,76 @@
+use ndarray::Array2;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum LinalgError {
+    #[error("matrix must be square, got {rows}x{cols}")]
+    NotSquare { rows: usize, cols: usize },
+
+    #[error("matrix is not positive definite: pivot {index} is {pivot}")]
+    NotPositiveDefinite { index: usize, pivot: f64 },
+
+    #[error("matrix is singular: diagonal term {index} is zero")]
+    Singular { index: usize },
+}
+
+// décomposition de Cholesky : renvoie L triangulaire inférieure telle que A = L * L^T
+pub fn cholesky(a: &Array2<f64>) -> Result<Array2<f64>, LinalgError> {
+    let (rows, cols) = a.dim();
+    if rows != cols {
+        return Err(LinalgError::NotSquare { rows, cols });
+    }
+
+    let n = rows;
+    let mut l = Array2::<f64>::zeros((n, n));
+
+    for j in 0..n {
+        // terme diagonal
+        let mut pivot = a[[j, j]];
+        for k in 0..j {
+            pivot -= l[[j, k]] * l[[j, k]];
+        }
+        if pivot <= 0.0 || pivot.is_nan() {
+            return Err(LinalgError::NotPositiveDefinite { index: j, pivot });
+        }
+        l[[j, j]] = pivot.sqrt();
+
+        // termes sous la diagonale
+        for i in (j + 1)..n {
+            let mut sum = a[[i, j]];
+            for k in 0..j {
+                sum -= l[[i, k]] * l[[j, k]];
+            }
+            l[[i, j]] = sum / l[[j, j]];
+        }
+    }
+
+    Ok(l)
+}
+
+// inverse d'une matrice triangulaire inférieure, par substitution avant colonne par colonne
+pub fn invert_lower_triangular(l: &Array2<f64>) -> Result<Array2<f64>, LinalgError> {
+    let (rows, cols) = l.dim();
+    if rows != cols {
+        return Err(LinalgError::NotSquare { rows, cols });
+    }
+
+    let n = rows;
+    let mut inverse = Array2::<f64>::zeros((n, n));
+
+    for i in 0..n {
+        if l[[i, i]] == 0.0 {
+            return Err(LinalgError::Singular { index: i });
+        }
+        inverse[[i, i]] = 1.0 / l[[i, i]];
+
+        for j in 0..i {
+            let mut sum = 0.0;
+            for k in j..i {
+                sum += l[[i, k]] * inverse[[k, j]];
+            }
+            inverse[[i, j]] = -sum / l[[i, i]];
+        }
+    }
+
+    Ok(inverse)
+}
@@ -1 +1,2 @@
+pub mod linalg;
 pub mod random;
@@ -1,7 +1,119 @@
-use rand::rngs::ThreadRng;
+use rand::Rng;
 use rand_distr::{Distribution, Normal};
 
-pub fn normal_vec(n: usize, rng: &mut ThreadRng, mean: f64, std_dev: f64) -> Vec<f64> {
-    let normal = Normal::new(mean, std_dev).unwrap();
-    (0..n).map(|_| normal.sample(rng)).collect()
+use crate::error::PcpdError;
+
+pub fn normal_vec<R: Rng + ?Sized>(
+    n: usize,
+    rng: &mut R,
+    mean: f64,
+    std_dev: f64,
+) -> Result<Vec<f64>, PcpdError> {
+    let normal = Normal::new(mean, std_dev)
+        .map_err(|e| PcpdError::Model(format!("invalid normal distribution: {}", e)))?;
+    Ok((0..n).map(|_| normal.sample(rng)).collect())
+}
+
+// inverse de la fonction de répartition de la loi normale centrée réduite
+// (algorithme d'Acklam, erreur relative < 1.15e-9 sur ]0, 1[)
+pub fn normal_inverse_cdf(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e+01,
+        2.209460984245205e+02,
+        -2.759285104469687e+02,
+        1.38357751867269e+02,
+        -3.066479806614716e+01,
+        2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01,
+        1.615858368580409e+02,
+        -1.556989798598866e+02,
+        6.680131188771972e+01,
+        -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03,
+        -3.223964580411365e-01,
+        -2.400758277161838e+00,
+        -2.549732539343734e+00,
+        4.374664141464968e+00,
+        2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03,
+        3.224671290700398e-01,
+        2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+
+    if p < P_LOW {
+        // queue gauche
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p <= 1.0 - P_LOW {
+        // région centrale
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        // queue droite, par symétrie
+        -normal_inverse_cdf(1.0 - p)
+    }
+}
+
+// fonction de répartition de la loi normale centrée réduite
+// (algorithme de Hart, dans la version de West, précision proche de la double précision)
+pub fn normal_cdf(x: f64) -> f64 {
+    const P: [f64; 7] = [
+        3.52624965998911e-02,
+        0.700383064443688,
+        6.37396220353165,
+        33.912866078383,
+        112.079291497871,
+        221.213596169931,
+        220.206867912376,
+    ];
+    const Q: [f64; 8] = [
+        8.83883476483184e-02,
+        1.75566716318264,
+        16.064177579207,
+        86.7807322029461,
+        296.564248779674,
+        637.333633378831,
+        793.826512519948,
+        440.413735824752,
+    ];
+
+    let y = x.abs();
+    let tail = if y > 37.0 {
+        0.0
+    } else if y < 7.07106781186547 {
+        let e = (-0.5 * y * y).exp();
+        let p = P.iter().fold(0.0, |b, c| b * y + c);
+        let q = Q.iter().fold(0.0, |b, c| b * y + c);
+        e * p / q
+    } else {
+        // fraction continue dans les queues
+        let e = (-0.5 * y * y).exp();
+        let b = y + 1.0 / (y + 2.0 / (y + 3.0 / (y + 4.0 / (y + 0.65))));
+        e / b / (2.0 * std::f64::consts::PI).sqrt()
+    };
+
+    if x > 0.0 { 1.0 - tail } else { tail }
+}
+
+// densité de la loi normale centrée réduite
+pub fn normal_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2.0 * std::f64::consts::PI).sqrt()
 }
@@ -0,0 +1,149 @@
+use ndarray::{Array1, Array2};
+
+use crate::config::PricingConfig;
+use crate::error::PcpdError;
+use crate::math::random::normal_cdf;
+use crate::model::dynamics::{Model, check_past};
+use crate::options::asian::AsianOption;
+use crate::options::basket::BasketOption;
+
+// variable de contrôle : payoff dont l'espérance sous la probabilité risque neutre est connue
+pub trait ControlVariate: Send + Sync {
+    fn payoff(&self, path: &Array2<f64>) -> Result<f64, PcpdError>;
+
+    // E[payoff | F_t], non actualisée, past contenant les fixings observés puis S_t
+    fn expectation(&self, model: &dyn Model, past: &Array2<f64>, t: f64) -> Result<f64, PcpdError>;
+}
+
+// option sur moyenne géométrique : (c * G - K)_+ avec log G = sum_i alpha_i sum_j w_j log S_j(t_i),
+// où c = sum_j lambda_j et w_j = lambda_j / c ; par l'inégalité arithmético-géométrique c * G
+// minore le panier, auquel il est très corrélé, et log G est gaussien sous Black-Scholes
+pub struct GeometricControl {
+    pub strike: f64,
+    pub scale: f64,                 // c = somme des coefficients du panier
+    pub asset_weights: Array1<f64>, // w_j
+    pub averaged: bool, // moyenne sur toutes les dates (asiatique) ou date finale (panier)
+}
+
+impl GeometricControl {
+    pub fn new(
+        strike: f64,
+        payoff_coefficients: &Array1<f64>,
+        averaged: bool,
+    ) -> Result<Self, PcpdError> {
+        if payoff_coefficients.is_empty() || payoff_coefficients.iter().any(|&x| x <= 0.0) {
+            return Err(PcpdError::Config(
+                "geometric control variate needs positive \"payoff coefficients\"".to_string(),
+            ));
+        }
+
+        let scale = payoff_coefficients.sum();
+        Ok(GeometricControl {
+            strike,
+            scale,
+            asset_weights: payoff_coefficients / scale,
+            averaged,
+        })
+    }
+
+    // contrôle de l'option asiatique : moyenne géométrique sur les N + 1 dates de fixing
+    pub fn asian(option: &AsianOption) -> Result<Self, PcpdError> {
+        Self::new(option.strike, &option.payoff_coeffcients, true)
+    }
+
+    // contrôle de l'option panier : moyenne géométrique des sous-jacents en T
+    pub fn basket(option: &BasketOption) -> Result<Self, PcpdError> {
+        Self::new(option.strike, &option.payoff_coeffcients, false)
+    }
+
+    pub fn from_config(config: &PricingConfig) -> Result<Self, PcpdError> {
+        match config.option_type.as_str() {
+            "asian" => Self::asian(&AsianOption::from_config(config)?),
+            "basket" => Self::basket(&BasketOption::from_config(config)?),
+            other => Err(PcpdError::Config(format!(
+                "no control variate for option type \"{}\" (expected \"asian\" or \"basket\")",
+                other
+            ))),
+        }
+    }
+
+    // poids alpha_i des N + 1 dates de fixing
+    fn time_weights(&self, dates: usize) -> Array1<f64> {
+        if self.averaged {
+            Array1::from_elem(dates, 1.0 / dates as f64)
+        } else {
+            let mut alpha = Array1::zeros(dates);
+            alpha[dates - 1] = 1.0;
+            alpha
+        }
+    }
+}
+
+impl ControlVariate for GeometricControl {
+    fn payoff(&self, path: &Array2<f64>) -> Result<f64, PcpdError> {
+        if path.ncols() != self.asset_weights.len() || path.nrows() == 0 {
+            return Err(PcpdError::Payoff(format!(
+                "path has shape {:?} but control variate expects {} assets",
+                path.shape(),
+                self.asset_weights.len()
+            )));
+        }
+
+        let alpha = self.time_weights(path.nrows());
+        let log_g = alpha.dot(&path.mapv(f64::ln).dot(&self.asset_weights));
+
+        Ok((self.scale * log_g.exp() - self.strike).max(0.0))
+    }
+
+    fn expectation(&self, model: &dyn Model, past: &Array2<f64>, t: f64) -> Result<f64, PcpdError> {
+        let model = model.as_black_scholes().ok_or_else(|| {
+            PcpdError::Model("geometric control variates need a Black-Scholes model".to_string())
+        })?;
+        let n = model.fixings_dates_number;
+        let r = model.interest_rate;
+        let last_index = model.last_fixing_index(t);
+        let alpha = self.time_weights(n + 1);
+
+        check_past(past, t, last_index, self.asset_weights.len())?;
+
+        let log_past = past.mapv(f64::ln);
+        let log_spot_t = log_past.row(past.nrows() - 1);
+
+        // drift risque neutre de log S_j et volatilité du facteur sum_j w_j sigma_j W_j
+        let drift = r - &model.dividend_rate - &model.volatility.mapv(|sigma| 0.5 * sigma * sigma);
+        let weighted_sigma = &self.asset_weights * &model.volatility;
+        let factor_variance = model
+            .l
+            .dot(&model.l.t())
+            .dot(&weighted_sigma)
+            .dot(&weighted_sigma);
+
+        // log G ~ N(mean, variance) sachant F_t
+        let mut mean = 0.0;
+        let mut variance = 0.0;
+        for i in 0..=n {
+            if i <= last_index {
+                mean += alpha[i] * log_past.row(i).dot(&self.asset_weights);
+                continue;
+            }
+
+            let tau_i = i as f64 * model.time_step - t;
+            mean += alpha[i] * (&log_spot_t + &(&drift * tau_i)).dot(&self.asset_weights);
+            for l in (last_index + 1)..=n {
+                let tau_l = l as f64 * model.time_step - t;
+                variance += alpha[i] * alpha[l] * tau_i.min(tau_l) * factor_variance;
+            }
+        }
+
+        if variance <= 0.0 {
+            return Ok((self.scale * mean.exp() - self.strike).max(0.0));
+        }
+
+        let std_dev = variance.sqrt();
+        let d2 = (mean + self.scale.ln() - self.strike.ln()) / std_dev;
+        let d1 = d2 + std_dev;
+
+        Ok(self.scale * (mean + 0.5 * variance).exp() * normal_cdf(d1)
+            - self.strike * normal_cdf(d2))
+    }
+}
@@ -0,0 +1,193 @@
+use std::str::FromStr;
+
+use ndarray::{Array1, Array2, Axis, s};
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::error::PcpdError;
+use crate::math::linalg::invert_lower_triangular;
+use crate::mc::pricer::{CHUNK_SIZE, Moments, MonteCarlo};
+use crate::mc::rng::{RandomSource, SplittableSource};
+use crate::model::black_scholes::BlackScholesModel;
+use crate::model::dynamics::Model;
+
+// estimateurs des sensibilités en t = 0 sans différences finies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GreeksMethod {
+    // dérivée trajectorielle du payoff (payoffs lipschitziens) ; gamma mixte LR / trajectoriel
+    Pathwise,
+    // payoff multiplié par la dérivée de la log-densité de la trajectoire (tout payoff)
+    LikelihoodRatio,
+}
+
+impl FromStr for GreeksMethod {
+    type Err = PcpdError;
+
+    fn from_str(method: &str) -> Result<Self, Self::Err> {
+        match method {
+            "pathwise" => Ok(GreeksMethod::Pathwise),
+            "lr" | "likelihood-ratio" => Ok(GreeksMethod::LikelihoodRatio),
+            other => Err(PcpdError::Config(format!(
+                "unknown greeks method \"{}\" (expected pathwise or likelihood-ratio)",
+                other
+            ))),
+        }
+    }
+}
+
+// sensibilités par actif : delta et gamma par rapport à S_0^j, vega par rapport à sigma_j
+#[derive(Debug, Clone, Serialize)]
+pub struct GreeksResult {
+    pub delta: Vec<f64>,
+    #[serde(rename = "deltaStdDev")]
+    pub delta_std_dev: Vec<f64>,
+    pub gamma: Vec<f64>,
+    #[serde(rename = "gammaStdDev")]
+    pub gamma_std_dev: Vec<f64>,
+    pub vega: Vec<f64>,
+    #[serde(rename = "vegaStdDev")]
+    pub vega_std_dev: Vec<f64>,
+}
+
+impl<M: Model> MonteCarlo<M> {
+    // delta, gamma et vega en t = 0 sur les trajectoires de BlackScholesModel::asset,
+    // découpées en paquets comme pour le prix (sans variables antithétiques ni de contrôle)
+    pub fn greeks<R: SplittableSource>(
+        &self,
+        method: GreeksMethod,
+        rng: &mut R,
+    ) -> Result<GreeksResult, PcpdError> {
+        let model = self.model.as_black_scholes().ok_or_else(|| {
+            PcpdError::Model("greeks estimators need a Black-Scholes model".to_string())
+        })?;
+        let d = model.model_size;
+
+        if model.volatility.iter().any(|&sigma| sigma <= 0.0) {
+            return Err(PcpdError::Model(
+                "greeks estimators need positive volatilities".to_string(),
+            ));
+        }
+        // C^{-1} = L^{-T} L^{-1}
+        let l_inverse = invert_lower_triangular(&model.l)?;
+
+        let chunk_sizes: Vec<usize> = (0..self.sample_number)
+            .step_by(CHUNK_SIZE)
+            .map(|start| CHUNK_SIZE.min(self.sample_number - start))
+            .collect();
+        let sources = rng.split(chunk_sizes.len(), CHUNK_SIZE);
+
+        // estimations [delta_1..d, gamma_1..d, vega_1..d] de chaque trajectoire
+        let moments = sources
+            .into_par_iter()
+            .zip(chunk_sizes)
+            .map(|(mut source, size)| {
+                let mut moments = Moments::zeros(3 * d);
+                for _ in 0..size {
+                    let estimate = self.greeks_sample(model, method, &l_inverse, &mut source)?;
+                    moments.add(&estimate);
+                }
+                Ok(moments)
+            })
+            .collect::<Result<Vec<Moments>, PcpdError>>()?
+            .into_iter()
+            .fold(Moments::zeros(3 * d), Moments::merge);
+
+        let discount = model.discount(0.0);
+        let m = moments.count as f64;
+        let mean = &moments.mean * discount;
+        let std_dev = (moments.variance() / m).mapv(f64::sqrt) * discount;
+
+        Ok(GreeksResult {
+            delta: mean.slice(s![..d]).to_vec(),
+            delta_std_dev: std_dev.slice(s![..d]).to_vec(),
+            gamma: mean.slice(s![d..2 * d]).to_vec(),
+            gamma_std_dev: std_dev.slice(s![d..2 * d]).to_vec(),
+            vega: mean.slice(s![2 * d..]).to_vec(),
+            vega_std_dev: std_dev.slice(s![2 * d..]).to_vec(),
+        })
+    }
+
+    // estimations non actualisées [delta, gamma, vega] sur une trajectoire
+    fn greeks_sample<R: RandomSource + ?Sized>(
+        &self,
+        model: &BlackScholesModel,
+        method: GreeksMethod,
+        l_inverse: &Array2<f64>,
+        rng: &mut R,
+    ) -> Result<Array1<f64>, PcpdError> {
+        let d = model.model_size;
+        let n = model.fixings_dates_number;
+        let r = model.interest_rate;
+        let dt = model.time_step;
+        let sqrt_dt = dt.sqrt();
+        let sigma = &model.volatility;
+        let q = &model.dividend_rate;
+
+        let path = model.asset(rng)?;
+        let spots = path.row(0);
+
+        // accroissements normalisés zeta_k = (log(S_k / S_{k-1}) - (r - q - sigma^2 / 2) dt) / (sigma sqrt(dt)),
+        // de corrélation C, et u_k = C^{-1} zeta_k = L^{-T} L^{-1} zeta_k
+        let mut zeta = Array2::<f64>::zeros((n, d));
+        for k in 0..n {
+            for j in 0..d {
+                let log_return = (path[[k + 1, j]] / path[[k, j]]).ln();
+                zeta[[k, j]] = (log_return - (r - q[j] - 0.5 * sigma[j] * sigma[j]) * dt)
+                    / (sigma[j] * sqrt_dt);
+            }
+        }
+        let u = zeta.dot(&l_inverse.t()).dot(l_inverse);
+        let c_inverse_diagonal = l_inverse.mapv(|x| x * x).sum_axis(Axis(0));
+
+        // score de S_0^j (seul le premier pas en dépend) et sa dérivée
+        let scale = &spots * sigma * sqrt_dt;
+        let score = &u.row(0) / &scale;
+        let score_derivative =
+            -&c_inverse_diagonal / (&scale * &scale) - &u.row(0) / (&scale * &spots);
+
+        let mut estimate = Array1::<f64>::zeros(3 * d);
+
+        match method {
+            GreeksMethod::LikelihoodRatio => {
+                let payoff = self.option.payoff(&path)?;
+
+                for j in 0..d {
+                    // d/dsigma_j log p = sum_k -u_kj (sqrt(dt) - zeta_kj / sigma_j) - 1 / sigma_j
+                    let vega_score: f64 = (0..n)
+                        .map(|k| -u[[k, j]] * (sqrt_dt - zeta[[k, j]] / sigma[j]) - 1.0 / sigma[j])
+                        .sum();
+
+                    estimate[j] = payoff * score[j];
+                    estimate[d + j] = payoff * (score[j] * score[j] + score_derivative[j]);
+                    estimate[2 * d + j] = payoff * vega_score;
+                }
+            }
+            GreeksMethod::Pathwise => {
+                let gradient = self.option.gradient(&path)?;
+
+                for j in 0..d {
+                    // S_{t_i}^j est proportionnel à S_0^j, et dS_{t_i}^j / dsigma_j = S_{t_i}^j (W_{t_i}^j - sigma_j t_i)
+                    let mut delta = 0.0;
+                    let mut vega = 0.0;
+                    for i in 0..=n {
+                        let s = path[[i, j]];
+                        let t_i = i as f64 * dt;
+                        delta += gradient[[i, j]] * s / spots[j];
+                        vega += gradient[[i, j]]
+                            * s
+                            * ((s / spots[j]).ln() - (r - q[j] + 0.5 * sigma[j] * sigma[j]) * t_i)
+                            / sigma[j];
+                    }
+
+                    // gamma : dérivée par LR de l'estimateur trajectoriel du delta, qui dépend
+                    // aussi explicitement de S_0^j
+                    estimate[j] = delta;
+                    estimate[d + j] = delta * score[j] - delta / spots[j];
+                    estimate[2 * d + j] = vega;
+                }
+            }
+        }
+
+        Ok(estimate)
+    }
+}
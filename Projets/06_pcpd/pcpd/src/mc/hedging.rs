@@ -0,0 +1,137 @@
+use std::time::Instant;
+
+use ndarray::{Array1, Array2, Axis};
+use serde::Serialize;
+
+use crate::config::PricingConfig;
+use crate::error::PcpdError;
+use crate::mc::pricer::MonteCarlo;
+use crate::mc::rng::SplittableSource;
+use crate::model::dynamics::{Model, TIME_EPSILON};
+use crate::options::option::Option;
+
+pub struct Hedger<M: Model = Box<dyn Model>> {
+    pub pricer: MonteCarlo<M>, // pricer utilisé à chaque date de rebalancement
+    pub hedging_dates_number: usize, // nombre de dates de rebalancement H
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct HedgingResult {
+    #[serde(rename = "finalPnL")]
+    pub final_pnl: f64, // erreur de couverture à maturité
+    #[serde(rename = "initialPrice")]
+    pub initial_price: f64, // prix en t = 0
+    #[serde(rename = "initialPriceStdDev")]
+    pub initial_price_std_dev: f64, // écart-type du prix en t = 0
+    pub time: f64, // temps de calcul en secondes
+}
+
+impl<M: Model> Hedger<M> {
+    pub fn new(pricer: MonteCarlo<M>, hedging_dates_number: usize) -> Result<Self, PcpdError> {
+        let hedger = Hedger {
+            pricer,
+            hedging_dates_number,
+        };
+        hedger.check_dates()?;
+        Ok(hedger)
+    }
+
+    // chaque date de fixing doit être une date de couverture : H multiple de N
+    fn check_dates(&self) -> Result<(), PcpdError> {
+        let h = self.hedging_dates_number;
+        let n = self.pricer.model.fixings_dates_number();
+
+        if h == 0 || !h.is_multiple_of(n) {
+            return Err(PcpdError::Config(format!(
+                "\"hedging dates number\" {} must be a positive multiple of the {} fixing dates",
+                h, n
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Hedger<Box<dyn Model>> {
+    pub fn from_config(config: &PricingConfig, option: Box<dyn Option>) -> Result<Self, PcpdError> {
+        let pricer = MonteCarlo::from_config(config, option)?;
+        Hedger::new(pricer, config.hedging_dates_number)
+    }
+}
+
+impl<M: Model> Hedger<M> {
+    // trajectoire observée en tau_i = i * T / H : fixings passés puis S_{tau_i}
+    pub fn past_from_market(&self, market: &Array2<f64>, i: usize) -> Array2<f64> {
+        let model = &self.pricer.model;
+        let hedging_step = model.maturity() / self.hedging_dates_number as f64;
+        let tau = i as f64 * hedging_step;
+
+        let last_index = model.last_fixing_index(tau);
+        let mut rows: Vec<usize> = (0..=last_index)
+            .map(|j| (j as f64 * model.time_step() / hedging_step).round() as usize)
+            .collect();
+
+        if (tau - last_index as f64 * model.time_step()).abs() > TIME_EPSILON {
+            rows.push(i);
+        }
+
+        market.select(Axis(0), &rows)
+    }
+
+    // couverture en delta le long de la trajectoire de marché, rebalancée aux H dates
+    pub fn hedge<R: SplittableSource>(
+        &self,
+        market: &Array2<f64>,
+        rng: &mut R,
+    ) -> Result<HedgingResult, PcpdError> {
+        let start = Instant::now();
+        self.check_dates()?;
+
+        let h = self.hedging_dates_number;
+        let model = &self.pricer.model;
+        let hedging_step = model.maturity() / h as f64;
+        let capitalisation = (model.interest_rate() * hedging_step).exp();
+        // dividendes versés sur une période par une part de chaque actif, en proportion de son prix
+        let dividend_yield = model.dividend_rate().mapv(|q| (q * hedging_step).exp_m1());
+
+        if market.nrows() != h + 1 || market.ncols() != model.model_size() {
+            return Err(PcpdError::Market(format!(
+                "expected {}x{} prices (hedging dates number + 1 rows, one column per asset), got {}x{}",
+                h + 1,
+                model.model_size(),
+                market.nrows(),
+                market.ncols()
+            )));
+        }
+
+        // Portefeuille initial : prix - delta * S_0 en cash
+        let past = self.past_from_market(market, 0);
+        let (initial, initial_delta) = self.pricer.price_and_delta_at(&past, 0.0, rng)?;
+
+        let mut delta: Array1<f64> = initial_delta.delta;
+        let mut cash = initial.price - delta.dot(&market.row(0));
+
+        // Rebalancements aux dates tau_1, ..., tau_{H-1}
+        for i in 1..h {
+            let tau = i as f64 * hedging_step;
+            let past = self.past_from_market(market, i);
+            let new_delta = self.pricer.delta_at(&past, tau, rng)?.delta;
+
+            let dividends = (&delta * &dividend_yield).dot(&market.row(i));
+            cash = cash * capitalisation + dividends - (&new_delta - &delta).dot(&market.row(i));
+            delta = new_delta;
+        }
+
+        // Liquidation à maturité
+        let past = self.past_from_market(market, h);
+        let payoff = self.pricer.option.payoff(&past)?;
+        let dividends = (&delta * &dividend_yield).dot(&market.row(h));
+        let final_pnl = cash * capitalisation + dividends + delta.dot(&market.row(h)) - payoff;
+
+        Ok(HedgingResult {
+            final_pnl,
+            initial_price: initial.price,
+            initial_price_std_dev: initial.price_std_dev,
+            time: start.elapsed().as_secs_f64(),
+        })
+    }
+}
@@ -0,0 +1,6 @@
+pub mod control;
+pub mod greeks;
+pub mod hedging;
+pub mod pricer;
+pub mod rng;
+pub mod sobol;
@@ -0,0 +1,612 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use ndarray::{Array1, Array2, ArrayView1, Axis};
+use rand::RngCore;
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::config::PricingConfig;
+use crate::error::PcpdError;
+use crate::mc::control::ControlVariate;
+use crate::mc::greeks::GreeksResult;
+use crate::mc::rng::{AntitheticSource, RandomSource, SplittableSource};
+use crate::mc::sobol::{SOBOL_MAX_DIMENSION, SobolSequence, SobolSource};
+use crate::model::dynamics::Model;
+use crate::model::factory::model_from_config;
+use crate::options::option::Option;
+
+// quantile à 97.5% de la loi normale centrée réduite (IC à 95%)
+const Z_95: f64 = 1.96;
+
+// nombre de trajectoires par paquet simulé sur un même flux aléatoire
+pub(crate) const CHUNK_SIZE: usize = 1000;
+
+// M : modèle des sous-jacents, choisi à l'exécution par "model type" par défaut
+pub struct MonteCarlo<M: Model = Box<dyn Model>> {
+    pub model: M,                                              // modèle des sous-jacents
+    pub option: Box<dyn Option>,                               // option à pricer
+    pub sample_number: usize,                                  // nombre de tirages M
+    pub fd_step: f64,                                          // pas h des différences finies
+    pub antithetic: bool, // trajectoires simulées par paires antithétiques
+    pub control: std::option::Option<Box<dyn ControlVariate>>, // variable de contrôle éventuelle
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PriceResult {
+    pub price: f64,                                  // prix actualisé
+    pub price_std_dev: f64,                          // écart-type de l'estimateur
+    pub confidence_interval: (f64, f64),             // intervalle de confiance à 95%
+    pub control: std::option::Option<ControlResult>, // estimation brute si price est contrôlé
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ControlResult {
+    pub raw_price: f64,         // prix sans variable de contrôle
+    pub raw_price_std_dev: f64, // écart-type de l'estimateur brut
+    pub coefficient: f64,       // coefficient b estimé de X - b (C - E[C])
+}
+
+impl PriceResult {
+    fn new(price: f64, price_std_dev: f64, control: std::option::Option<ControlResult>) -> Self {
+        PriceResult {
+            price,
+            price_std_dev,
+            confidence_interval: (price - Z_95 * price_std_dev, price + Z_95 * price_std_dev),
+            control,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PricingResult {
+    pub time: f64, // temps de calcul en secondes
+    pub delta: Vec<f64>,
+    #[serde(rename = "deltaStdDev")]
+    pub delta_std_dev: Vec<f64>,
+    pub price: f64,
+    #[serde(rename = "priceStdDev")]
+    pub price_std_dev: f64,
+    #[serde(
+        rename = "rawPrice",
+        skip_serializing_if = "std::option::Option::is_none"
+    )]
+    pub raw_price: std::option::Option<f64>,
+    #[serde(
+        rename = "rawPriceStdDev",
+        skip_serializing_if = "std::option::Option::is_none"
+    )]
+    pub raw_price_std_dev: std::option::Option<f64>,
+    #[serde(
+        rename = "controlCoefficient",
+        skip_serializing_if = "std::option::Option::is_none"
+    )]
+    pub control_coefficient: std::option::Option<f64>,
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub greeks: std::option::Option<GreeksResult>, // estimateurs trajectoriel ou LR, si demandés
+}
+
+impl PricingResult {
+    fn new(start: Instant, price: &PriceResult, delta: &DeltaResult) -> Self {
+        PricingResult {
+            time: start.elapsed().as_secs_f64(),
+            delta: delta.delta.to_vec(),
+            delta_std_dev: delta.delta_std_dev.to_vec(),
+            price: price.price,
+            price_std_dev: price.price_std_dev,
+            raw_price: price.control.map(|c| c.raw_price),
+            raw_price_std_dev: price.control.map(|c| c.raw_price_std_dev),
+            control_coefficient: price.control.map(|c| c.coefficient),
+            greeks: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeltaResult {
+    pub delta: Array1<f64>,         // deltas par actif
+    pub delta_std_dev: Array1<f64>, // écarts-types des deltas
+}
+
+impl<M: Model> MonteCarlo<M> {
+    pub fn new(model: M, option: Box<dyn Option>, sample_number: usize, fd_step: f64) -> Self {
+        MonteCarlo {
+            model,
+            option,
+            sample_number,
+            fd_step,
+            antithetic: false,
+            control: None,
+        }
+    }
+}
+
+impl MonteCarlo<Box<dyn Model>> {
+    pub fn from_config(config: &PricingConfig, option: Box<dyn Option>) -> Result<Self, PcpdError> {
+        if config.sample_number == 0 {
+            return Err(PcpdError::Config(
+                "\"sample number\" must be positive".to_string(),
+            ));
+        }
+        if config.fd_step <= 0.0 {
+            return Err(PcpdError::Config(
+                "\"fd step\" must be positive".to_string(),
+            ));
+        }
+
+        let model = model_from_config(config)?;
+        Ok(MonteCarlo::new(
+            model,
+            option,
+            config.sample_number,
+            config.fd_step,
+        ))
+    }
+}
+
+impl<M: Model> MonteCarlo<M> {
+    // prix en t = 0
+    pub fn price<R: SplittableSource>(&self, rng: &mut R) -> Result<PriceResult, PcpdError> {
+        let past = self.model.spots().to_owned().insert_axis(Axis(0));
+        self.price_at(&past, 0.0, rng)
+    }
+
+    // prix en t, conditionnellement à la trajectoire passée
+    pub fn price_at<R: SplittableSource>(
+        &self,
+        past: &Array2<f64>,
+        t: f64,
+        rng: &mut R,
+    ) -> Result<PriceResult, PcpdError> {
+        let sums = self.simulate(past, t, false, self.sample_number, rng)?;
+
+        let discount = self.model.discount(t);
+        Ok(price_result(
+            discount,
+            &sums,
+            self.control_expectation(past, t)?,
+        ))
+    }
+
+    // deltas en t = 0
+    pub fn delta<R: SplittableSource>(&self, rng: &mut R) -> Result<DeltaResult, PcpdError> {
+        let past = self.model.spots().to_owned().insert_axis(Axis(0));
+        self.delta_at(&past, 0.0, rng)
+    }
+
+    // prix et deltas en t = 0, au format des fichiers *_expected_price.json
+    pub fn price_and_delta<R: SplittableSource>(
+        &self,
+        rng: &mut R,
+    ) -> Result<PricingResult, PcpdError> {
+        let start = Instant::now();
+
+        let past = self.model.spots().to_owned().insert_axis(Axis(0));
+        let (price, delta) = self.price_and_delta_at(&past, 0.0, rng)?;
+
+        Ok(PricingResult::new(start, &price, &delta))
+    }
+
+    pub fn delta_at<R: SplittableSource>(
+        &self,
+        past: &Array2<f64>,
+        t: f64,
+        rng: &mut R,
+    ) -> Result<DeltaResult, PcpdError> {
+        Ok(self.price_and_delta_at(past, t, rng)?.1)
+    }
+
+    // prix et deltas en t calculés sur les mêmes trajectoires ;
+    // les deltas sont des différences finies centrées sur des copies translatées de chaque trajectoire
+    pub fn price_and_delta_at<R: SplittableSource>(
+        &self,
+        past: &Array2<f64>,
+        t: f64,
+        rng: &mut R,
+    ) -> Result<(PriceResult, DeltaResult), PcpdError> {
+        let d = self.model.model_size();
+        let h = self.fd_step;
+        let sums = self.simulate(past, t, true, self.sample_number, rng)?;
+        let m = sums.count as f64;
+
+        let discount = self.model.discount(t);
+
+        let spot_t = past.row(past.nrows() - 1);
+        let mut delta = Array1::<f64>::zeros(d);
+        let mut delta_std_dev = Array1::<f64>::zeros(d);
+
+        for j in 0..d {
+            let factor = discount / (2.0 * h * spot_t[j]);
+            let diff_mean = sums.diff.mean[j];
+            let diff_variance = sums.diff.m2[j] / m;
+
+            delta[j] = factor * diff_mean;
+            delta_std_dev[j] = factor * (diff_variance / m).sqrt();
+        }
+
+        Ok((
+            price_result(discount, &sums, self.control_expectation(past, t)?),
+            DeltaResult {
+                delta,
+                delta_std_dev,
+            },
+        ))
+    }
+
+    // prix et deltas en t = 0 par quasi-Monte Carlo randomisé
+    pub fn price_and_delta_rqmc<R: RngCore + ?Sized>(
+        &self,
+        replications: usize,
+        rng: &mut R,
+    ) -> Result<PricingResult, PcpdError> {
+        let start = Instant::now();
+
+        let past = self.model.spots().to_owned().insert_axis(Axis(0));
+        let (price, delta) = self.price_and_delta_rqmc_at(&past, 0.0, replications, rng)?;
+
+        Ok(PricingResult::new(start, &price, &delta))
+    }
+
+    // les M tirages sont répartis en `replications` suites de Sobol indépendamment décalées ;
+    // chaque suite donne une estimation sans biais, et l'écart-type est estimé à partir
+    // de la dispersion de ces estimations
+    pub fn price_and_delta_rqmc_at<R: RngCore + ?Sized>(
+        &self,
+        past: &Array2<f64>,
+        t: f64,
+        replications: usize,
+        rng: &mut R,
+    ) -> Result<(PriceResult, DeltaResult), PcpdError> {
+        let d = self.model.model_size();
+        let h = self.fd_step;
+
+        if replications < 2 {
+            return Err(PcpdError::Config(
+                "randomized QMC needs at least 2 replications".to_string(),
+            ));
+        }
+        let points = self.sample_number / replications;
+        if points == 0 {
+            return Err(PcpdError::Config(format!(
+                "\"sample number\" {} is smaller than the {} replications",
+                self.sample_number, replications
+            )));
+        }
+
+        // une coordonnée par gaussienne d'une trajectoire
+        let dimension = self.model.gaussians_number(t);
+        if dimension > SOBOL_MAX_DIMENSION {
+            return Err(PcpdError::Config(format!(
+                "randomized QMC needs {} gaussians per path, the Sobol sequence provides at most {}",
+                dimension, SOBOL_MAX_DIMENSION
+            )));
+        }
+        let sequence = Arc::new(SobolSequence::new(dimension.max(1))?);
+
+        let discount = self.model.discount(t);
+        let control_expectation = self.control_expectation(past, t)?;
+        let spot_t = past.row(past.nrows() - 1);
+
+        let mut prices = Vec::with_capacity(replications);
+        let mut raw_prices = Vec::with_capacity(replications);
+        let mut coefficients = Vec::with_capacity(replications);
+        let mut deltas = Array2::<f64>::zeros((replications, d));
+
+        for k in 0..replications {
+            let mut source = SobolSource::new(sequence.clone(), &self.model, t, rng)?;
+            let sums = self.simulate(past, t, true, points, &mut source)?;
+
+            let result = price_result(discount, &sums, control_expectation);
+            prices.push(result.price);
+            if let Some(control) = result.control {
+                raw_prices.push(control.raw_price);
+                coefficients.push(control.coefficient);
+            }
+            for j in 0..d {
+                let factor = discount / (2.0 * h * spot_t[j]);
+                deltas[[k, j]] = factor * sums.diff.mean[j];
+            }
+        }
+
+        let (price, price_std_dev) = replication_mean(&Array1::from(prices).view());
+        let control = control_expectation.map(|_| {
+            let (raw_price, raw_price_std_dev) = replication_mean(&Array1::from(raw_prices).view());
+            ControlResult {
+                raw_price,
+                raw_price_std_dev,
+                coefficient: coefficients.iter().sum::<f64>() / replications as f64,
+            }
+        });
+        let mut delta = Array1::<f64>::zeros(d);
+        let mut delta_std_dev = Array1::<f64>::zeros(d);
+        for j in 0..d {
+            (delta[j], delta_std_dev[j]) = replication_mean(&deltas.column(j));
+        }
+
+        Ok((
+            PriceResult::new(price, price_std_dev, control),
+            DeltaResult {
+                delta,
+                delta_std_dev,
+            },
+        ))
+    }
+
+    // les tirages sont répartis en paquets de CHUNK_SIZE tirages, chacun avec son propre flux
+    // aléatoire : le découpage ne dépend pas du nombre de threads, donc le résultat non plus ;
+    // en antithétique, un tirage est une paire de trajectoires et M trajectoires font M / 2 tirages
+    fn simulate<R: SplittableSource>(
+        &self,
+        past: &Array2<f64>,
+        t: f64,
+        with_delta: bool,
+        path_number: usize,
+        rng: &mut R,
+    ) -> Result<Sums, PcpdError> {
+        let sample_number = match self.antithetic {
+            true => path_number.div_ceil(2),
+            false => path_number,
+        };
+
+        let chunk_sizes: Vec<usize> = (0..sample_number)
+            .step_by(CHUNK_SIZE)
+            .map(|start| CHUNK_SIZE.min(sample_number - start))
+            .collect();
+        let sources = rng.split(chunk_sizes.len(), CHUNK_SIZE);
+
+        let partial_sums = sources
+            .into_par_iter()
+            .zip(chunk_sizes)
+            .map(|(mut source, size)| self.simulate_chunk(past, t, with_delta, size, &mut source))
+            .collect::<Result<Vec<Sums>, PcpdError>>()?;
+
+        // fusion dans l'ordre des paquets
+        let d = if with_delta {
+            self.model.model_size()
+        } else {
+            0
+        };
+        Ok(partial_sums.into_iter().fold(Sums::zeros(d), Sums::merge))
+    }
+
+    fn simulate_chunk<R: RandomSource>(
+        &self,
+        past: &Array2<f64>,
+        t: f64,
+        with_delta: bool,
+        size: usize,
+        rng: &mut R,
+    ) -> Result<Sums, PcpdError> {
+        let d = if with_delta {
+            self.model.model_size()
+        } else {
+            0
+        };
+        let mut sums = Sums::zeros(d);
+
+        if self.antithetic {
+            let mut rng = AntitheticSource::new(rng);
+            for _ in 0..size {
+                // moyenne de la paire : les tirages restent i.i.d
+                let (payoff, control, diff) = self.sample(past, t, d, &mut rng)?;
+                let (payoff_bar, control_bar, diff_bar) = self.sample(past, t, d, &mut rng)?;
+                sums.add(
+                    0.5 * (payoff + payoff_bar),
+                    0.5 * (control + control_bar),
+                    0.5 * (diff + diff_bar),
+                );
+            }
+        } else {
+            for _ in 0..size {
+                let (payoff, control, diff) = self.sample(past, t, d, rng)?;
+                sums.add(payoff, control, diff);
+            }
+        }
+
+        Ok(sums)
+    }
+
+    // payoff d'une trajectoire, payoff de la variable de contrôle (0 sans contrôle)
+    // et différences payoff(S^{+h}) - payoff(S^{-h}) pour les d premiers actifs
+    fn sample<R: RandomSource>(
+        &self,
+        past: &Array2<f64>,
+        t: f64,
+        d: usize,
+        rng: &mut R,
+    ) -> Result<(f64, f64, Array1<f64>), PcpdError> {
+        let h = self.fd_step;
+        let path = self.model.asset_from(past, t, rng)?;
+        let payoff = self.option.payoff(&path)?;
+        let control = match &self.control {
+            Some(control) => control.payoff(&path)?,
+            None => 0.0,
+        };
+
+        let mut diff = Array1::<f64>::zeros(d);
+        for j in 0..d {
+            let up = self.model.shift_asset(&path, j, h, t);
+            let down = self.model.shift_asset(&path, j, -h, t);
+            diff[j] = self.option.payoff(&up)? - self.option.payoff(&down)?;
+        }
+
+        Ok((payoff, control, diff))
+    }
+
+    fn control_expectation(
+        &self,
+        past: &Array2<f64>,
+        t: f64,
+    ) -> Result<std::option::Option<f64>, PcpdError> {
+        self.control
+            .as_ref()
+            .map(|control| control.expectation(&self.model, past, t))
+            .transpose()
+    }
+}
+
+// moyennes et moments centrés des payoffs (et des différences finies) sur un paquet de tirages,
+// mis à jour par l'algorithme de Welford et fusionnés par celui de Chan : la formule
+// E[X^2] - E[X]^2 perd toute précision quand la variance est petite devant la moyenne
+struct Sums {
+    count: usize,
+    mean: f64,
+    m2: f64, // somme des carrés des écarts à la moyenne
+    control_mean: f64,
+    control_m2: f64,
+    co_moment: f64, // somme des produits des écarts du payoff et du contrôle
+    diff: Moments,
+}
+
+impl Sums {
+    fn zeros(d: usize) -> Self {
+        Sums {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            control_mean: 0.0,
+            control_m2: 0.0,
+            co_moment: 0.0,
+            diff: Moments::zeros(d),
+        }
+    }
+
+    fn add(&mut self, payoff: f64, control: f64, diff: Array1<f64>) {
+        self.count += 1;
+        let n = self.count as f64;
+
+        let payoff_gap = payoff - self.mean;
+        let control_gap = control - self.control_mean;
+        self.mean += payoff_gap / n;
+        self.control_mean += control_gap / n;
+        self.m2 += payoff_gap * (payoff - self.mean);
+        self.control_m2 += control_gap * (control - self.control_mean);
+        self.co_moment += payoff_gap * (control - self.control_mean);
+
+        self.diff.add(&diff);
+    }
+
+    fn merge(self, other: Sums) -> Self {
+        if other.count == 0 {
+            return self;
+        }
+        if self.count == 0 {
+            return other;
+        }
+
+        let (a, b) = (self.count as f64, other.count as f64);
+        let n = a + b;
+        let payoff_gap = other.mean - self.mean;
+        let control_gap = other.control_mean - self.control_mean;
+
+        Sums {
+            count: self.count + other.count,
+            mean: self.mean + payoff_gap * b / n,
+            m2: self.m2 + other.m2 + payoff_gap * payoff_gap * a * b / n,
+            control_mean: self.control_mean + control_gap * b / n,
+            control_m2: self.control_m2 + other.control_m2 + control_gap * control_gap * a * b / n,
+            co_moment: self.co_moment + other.co_moment + payoff_gap * control_gap * a * b / n,
+            diff: self.diff.merge(other.diff),
+        }
+    }
+}
+
+// moyenne et somme des carrés des écarts à la moyenne de chaque coordonnée d'un vecteur
+// de tirages, par les mêmes algorithmes de Welford et de Chan
+pub(crate) struct Moments {
+    pub(crate) count: usize,
+    pub(crate) mean: Array1<f64>,
+    pub(crate) m2: Array1<f64>,
+}
+
+impl Moments {
+    pub(crate) fn zeros(d: usize) -> Self {
+        Moments {
+            count: 0,
+            mean: Array1::zeros(d),
+            m2: Array1::zeros(d),
+        }
+    }
+
+    pub(crate) fn add(&mut self, x: &Array1<f64>) {
+        self.count += 1;
+        let gap = x - &self.mean;
+        self.mean += &(&gap / self.count as f64);
+        self.m2 += &(&gap * &(x - &self.mean));
+    }
+
+    pub(crate) fn merge(self, other: Moments) -> Self {
+        if other.count == 0 {
+            return self;
+        }
+        if self.count == 0 {
+            return other;
+        }
+
+        let (a, b) = (self.count as f64, other.count as f64);
+        let n = a + b;
+        let gap = &other.mean - &self.mean;
+
+        Moments {
+            count: self.count + other.count,
+            mean: &self.mean + &(&gap * (b / n)),
+            m2: self.m2 + other.m2 + &gap * &gap * (a * b / n),
+        }
+    }
+
+    // variance empirique (biaisée) de chaque coordonnée
+    pub(crate) fn variance(&self) -> Array1<f64> {
+        &self.m2 / self.count as f64
+    }
+}
+
+// moyenne des estimations indépendantes et écart-type de cette moyenne
+fn replication_mean(estimates: &ArrayView1<f64>) -> (f64, f64) {
+    let r = estimates.len() as f64;
+    let mean = estimates.sum() / r;
+    let variance = estimates.mapv(|x| (x - mean) * (x - mean)).sum() / (r - 1.0);
+
+    (mean, (variance / r).sqrt())
+}
+
+// prix actualisé, écart-type et IC à partir des sommes des payoffs ; avec une variable de
+// contrôle C d'espérance connue, l'estimateur est X - b (C - E[C]) avec b = Cov(X, C) / Var(C)
+// estimé sur les mêmes tirages, de variance Var(X) - Cov(X, C)^2 / Var(C)
+fn price_result(
+    discount: f64,
+    sums: &Sums,
+    control_expectation: std::option::Option<f64>,
+) -> PriceResult {
+    let m = sums.count as f64;
+    let mean = sums.mean;
+    let variance = sums.m2 / m;
+
+    let raw_price = discount * mean;
+    let raw_price_std_dev = discount * (variance / m).sqrt();
+
+    let Some(expectation) = control_expectation else {
+        return PriceResult::new(raw_price, raw_price_std_dev, None);
+    };
+
+    let control_mean = sums.control_mean;
+    let control_variance = sums.control_m2 / m;
+    let covariance = sums.co_moment / m;
+    let coefficient = if control_variance > 0.0 {
+        covariance / control_variance
+    } else {
+        0.0
+    };
+
+    let controlled_mean = mean - coefficient * (control_mean - expectation);
+    let controlled_variance = (variance - coefficient * covariance).max(0.0);
+
+    PriceResult::new(
+        discount * controlled_mean,
+        discount * (controlled_variance / m).sqrt(),
+        Some(ControlResult {
+            raw_price,
+            raw_price_std_dev,
+            coefficient,
+        }),
+    )
+}
@@ -0,0 +1,160 @@
+use rand::{RngCore, SeedableRng};
+use rand_distr::{Distribution, StandardNormal};
+
+// source de gaussiennes utilisée par les modèles pour simuler les trajectoires
+pub trait RandomSource {
+    // vecteur de n gaussiennes centrées réduites i.i.d
+    fn normal_vec(&mut self, n: usize) -> Vec<f64>;
+}
+
+// tout générateur de rand (ThreadRng, StdRng, Xoshiro256PlusPlus, ...) est une source
+impl<R: RngCore + ?Sized> RandomSource for R {
+    fn normal_vec(&mut self, n: usize) -> Vec<f64> {
+        (0..n).map(|_| StandardNormal.sample(self)).collect()
+    }
+}
+
+// variables antithétiques : chaque vecteur tiré est suivi de son opposé,
+// de sorte que deux trajectoires consécutives forment une paire (g, -g)
+pub struct AntitheticSource<'a, R: RandomSource + ?Sized> {
+    source: &'a mut R,
+    pending: Option<Vec<f64>>, // opposé du dernier vecteur tiré, pas encore utilisé
+}
+
+impl<'a, R: RandomSource + ?Sized> AntitheticSource<'a, R> {
+    pub fn new(source: &'a mut R) -> Self {
+        AntitheticSource {
+            source,
+            pending: None,
+        }
+    }
+}
+
+impl<R: RandomSource + ?Sized> RandomSource for AntitheticSource<'_, R> {
+    fn normal_vec(&mut self, n: usize) -> Vec<f64> {
+        if let Some(g) = self.pending.take() {
+            // un seul appel par trajectoire (cf. Model::asset_from) : sinon les paires sont rompues
+            debug_assert_eq!(
+                g.len(),
+                n,
+                "antithetic source expects one normal_vec call of the same size per path"
+            );
+            if g.len() == n {
+                return g;
+            }
+        }
+
+        let g = self.source.normal_vec(n);
+        self.pending = Some(g.iter().map(|x| -x).collect());
+        g
+    }
+}
+
+// source découpable en flux indépendants, pour la simulation en parallèle
+pub trait SplittableSource: RandomSource + Send + Sized {
+    // `chunks` sources, chacune destinée à simuler au plus `chunk_size` trajectoires ;
+    // la source courante avance de sorte qu'un second appel donne de nouveaux flux
+    fn split(&mut self, chunks: usize, chunk_size: usize) -> Vec<Self>;
+}
+
+// générateur xoshiro256++ : suite identique pour une graine donnée, quelle que soit
+// la version de rand (contrairement à StdRng)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Xoshiro256PlusPlus {
+    s: [u64; 4],
+}
+
+// polynôme de saut : jump() équivaut à 2^128 appels à next_u64()
+const JUMP: [u64; 4] = [
+    0x180ec6d33cfd0aba,
+    0xd5a61266f0c9392c,
+    0xa9582618e03fc9aa,
+    0x39abdc4529b1661c,
+];
+
+impl Xoshiro256PlusPlus {
+    // graine aléatoire, pour les tirages non reproductibles
+    pub fn from_entropy() -> Self {
+        Self::seed_from_u64(rand::rng().next_u64())
+    }
+
+    // avance l'état de 2^128 tirages : les flux successifs ne se chevauchent pas
+    pub fn jump(&mut self) {
+        let mut s = [0u64; 4];
+
+        for jump in JUMP {
+            for b in 0..64 {
+                if jump & (1u64 << b) != 0 {
+                    for (x, y) in s.iter_mut().zip(self.s.iter()) {
+                        *x ^= y;
+                    }
+                }
+                self.next_u64();
+            }
+        }
+
+        self.s = s;
+    }
+}
+
+impl SplittableSource for Xoshiro256PlusPlus {
+    // le paquet k part de l'état courant avancé de k sauts
+    fn split(&mut self, chunks: usize, _chunk_size: usize) -> Vec<Self> {
+        (0..chunks)
+            .map(|_| {
+                let source = self.clone();
+                self.jump();
+                source
+            })
+            .collect()
+    }
+}
+
+impl RngCore for Xoshiro256PlusPlus {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let result = (self.s[0].wrapping_add(self.s[3]))
+            .rotate_left(23)
+            .wrapping_add(self.s[0]);
+
+        let t = self.s[1] << 17;
+
+        self.s[2] ^= self.s[0];
+        self.s[3] ^= self.s[1];
+        self.s[1] ^= self.s[2];
+        self.s[0] ^= self.s[3];
+
+        self.s[2] ^= t;
+        self.s[3] = self.s[3].rotate_left(45);
+
+        result
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        for chunk in dst.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+}
+
+impl SeedableRng for Xoshiro256PlusPlus {
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut s = [0u64; 4];
+        for (i, chunk) in seed.chunks_exact(8).enumerate() {
+            s[i] = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+
+        // l'état nul est un point fixe du générateur
+        if s == [0; 4] {
+            return Self::seed_from_u64(0);
+        }
+
+        Xoshiro256PlusPlus { s }
+    }
+}
@@ -0,0 +1,301 @@
+use std::sync::Arc;
+
+use rand::RngCore;
+
+use crate::error::PcpdError;
+use crate::math::random::normal_inverse_cdf;
+use crate::mc::rng::{RandomSource, SplittableSource};
+use crate::model::dynamics::Model;
+
+// nombre de bits des points : au plus 2^32 points
+const BITS: usize = 32;
+
+// nombres directeurs de Joe et Kuo (fichier new-joe-kuo-6.21201, critère D(6) sur les
+// projections en dimension 2) pour les dimensions 2, 3, ... : degré s du polynôme primitif,
+// coefficients a (a_1 en bit de poids fort) et m_1, ..., m_s
+const JOE_KUO: [(u32, u32, &[u32]); 100] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+    (6, 19, &[1, 1, 1, 15, 7, 5]),
+    (6, 22, &[1, 3, 1, 15, 13, 25]),
+    (6, 25, &[1, 1, 5, 5, 19, 61]),
+    (7, 1, &[1, 3, 7, 11, 23, 15, 103]),
+    (7, 4, &[1, 3, 7, 13, 13, 15, 69]),
+    (7, 7, &[1, 1, 3, 13, 7, 35, 63]),
+    (7, 8, &[1, 3, 5, 9, 1, 25, 53]),
+    (7, 14, &[1, 3, 1, 13, 9, 35, 107]),
+    (7, 19, &[1, 3, 1, 5, 27, 61, 31]),
+    (7, 21, &[1, 1, 5, 11, 19, 41, 61]),
+    (7, 28, &[1, 3, 5, 3, 3, 13, 69]),
+    (7, 31, &[1, 1, 7, 13, 1, 19, 1]),
+    (7, 32, &[1, 3, 7, 5, 13, 19, 59]),
+    (7, 37, &[1, 1, 3, 9, 25, 29, 41]),
+    (7, 41, &[1, 3, 5, 13, 23, 1, 55]),
+    (7, 42, &[1, 3, 7, 3, 13, 59, 17]),
+    (7, 50, &[1, 3, 1, 3, 5, 53, 69]),
+    (7, 55, &[1, 1, 5, 5, 23, 33, 13]),
+    (7, 56, &[1, 1, 7, 7, 1, 61, 123]),
+    (7, 59, &[1, 1, 7, 9, 13, 61, 49]),
+    (7, 62, &[1, 3, 3, 5, 3, 55, 33]),
+    (8, 14, &[1, 3, 1, 15, 31, 13, 49, 245]),
+    (8, 21, &[1, 3, 5, 15, 31, 59, 63, 97]),
+    (8, 22, &[1, 3, 1, 11, 11, 11, 77, 249]),
+    (8, 38, &[1, 3, 1, 11, 27, 43, 71, 9]),
+    (8, 47, &[1, 1, 7, 15, 21, 11, 81, 45]),
+    (8, 49, &[1, 3, 7, 3, 25, 31, 65, 79]),
+    (8, 50, &[1, 3, 1, 1, 19, 11, 3, 205]),
+    (8, 52, &[1, 1, 5, 9, 19, 21, 29, 157]),
+    (8, 56, &[1, 3, 7, 11, 1, 33, 89, 185]),
+    (8, 67, &[1, 3, 3, 3, 15, 9, 79, 71]),
+    (8, 70, &[1, 3, 7, 11, 15, 39, 119, 27]),
+    (8, 84, &[1, 1, 3, 1, 11, 31, 97, 225]),
+    (8, 97, &[1, 1, 1, 3, 23, 43, 57, 177]),
+    (8, 103, &[1, 3, 7, 7, 17, 17, 37, 71]),
+    (8, 115, &[1, 3, 1, 5, 27, 63, 123, 213]),
+    (8, 122, &[1, 1, 3, 5, 11, 43, 53, 133]),
+    (9, 8, &[1, 3, 5, 5, 29, 17, 47, 173, 479]),
+    (9, 13, &[1, 3, 3, 11, 3, 1, 109, 9, 69]),
+    (9, 16, &[1, 1, 1, 5, 17, 39, 23, 5, 343]),
+    (9, 22, &[1, 3, 1, 5, 25, 15, 31, 103, 499]),
+    (9, 25, &[1, 1, 1, 11, 11, 17, 63, 105, 183]),
+    (9, 44, &[1, 1, 5, 11, 9, 29, 97, 231, 363]),
+    (9, 47, &[1, 1, 5, 15, 19, 45, 41, 7, 383]),
+    (9, 52, &[1, 3, 7, 7, 31, 19, 83, 137, 221]),
+    (9, 55, &[1, 1, 1, 3, 23, 15, 111, 223, 83]),
+    (9, 59, &[1, 1, 5, 13, 31, 15, 55, 25, 161]),
+    (9, 62, &[1, 1, 3, 13, 25, 47, 39, 87, 257]),
+    (9, 67, &[1, 1, 1, 11, 21, 53, 125, 249, 293]),
+    (9, 74, &[1, 1, 7, 11, 11, 7, 57, 79, 323]),
+    (9, 81, &[1, 1, 5, 5, 17, 13, 81, 3, 131]),
+    (9, 82, &[1, 1, 7, 13, 23, 7, 65, 251, 475]),
+    (9, 87, &[1, 3, 5, 1, 9, 43, 3, 149, 11]),
+    (9, 91, &[1, 1, 3, 13, 31, 13, 13, 255, 487]),
+    (9, 94, &[1, 3, 3, 1, 5, 63, 89, 91, 127]),
+    (9, 103, &[1, 1, 3, 3, 1, 19, 123, 127, 237]),
+    (9, 104, &[1, 1, 5, 7, 23, 31, 37, 243, 289]),
+    (9, 109, &[1, 1, 5, 11, 17, 53, 117, 183, 491]),
+    (9, 122, &[1, 1, 1, 5, 1, 13, 13, 209, 345]),
+    (9, 124, &[1, 1, 3, 15, 1, 57, 115, 7, 33]),
+    (9, 137, &[1, 3, 1, 11, 7, 43, 81, 207, 175]),
+    (9, 138, &[1, 3, 1, 1, 15, 27, 63, 255, 49]),
+    (9, 143, &[1, 3, 5, 3, 27, 61, 105, 171, 305]),
+    (9, 145, &[1, 1, 5, 3, 1, 3, 57, 249, 149]),
+    (9, 152, &[1, 1, 3, 5, 5, 57, 15, 13, 159]),
+    (9, 157, &[1, 1, 1, 11, 7, 11, 105, 141, 225]),
+    (9, 167, &[1, 3, 3, 5, 27, 59, 121, 101, 271]),
+    (9, 173, &[1, 3, 5, 9, 11, 49, 51, 59, 115]),
+    (9, 176, &[1, 1, 7, 1, 23, 45, 125, 71, 419]),
+    (9, 181, &[1, 1, 3, 5, 23, 5, 105, 109, 75]),
+    (9, 182, &[1, 1, 7, 15, 7, 11, 67, 121, 453]),
+    (9, 185, &[1, 3, 7, 3, 9, 13, 31, 27, 449]),
+    (9, 191, &[1, 3, 1, 15, 19, 39, 39, 89, 15]),
+    (9, 194, &[1, 1, 1, 1, 1, 33, 73, 145, 379]),
+    (9, 199, &[1, 3, 1, 15, 15, 43, 29, 13, 483]),
+    (9, 218, &[1, 1, 7, 3, 19, 27, 85, 131, 431]),
+    (9, 220, &[1, 3, 3, 3, 5, 35, 23, 195, 349]),
+    (9, 227, &[1, 3, 3, 7, 9, 27, 39, 59, 297]),
+    (9, 229, &[1, 1, 3, 9, 11, 17, 13, 241, 157]),
+    (9, 230, &[1, 3, 7, 15, 25, 57, 33, 189, 213]),
+    (9, 234, &[1, 1, 7, 1, 9, 55, 73, 83, 217]),
+    (9, 236, &[1, 3, 3, 13, 19, 27, 23, 113, 249]),
+    (9, 241, &[1, 3, 5, 3, 23, 43, 3, 253, 479]),
+    (9, 244, &[1, 1, 5, 5, 11, 5, 45, 117, 217]),
+    (9, 253, &[1, 3, 3, 7, 29, 37, 33, 123, 147]),
+];
+
+// la première dimension (van der Corput) n'a pas besoin de table
+pub const SOBOL_MAX_DIMENSION: usize = JOE_KUO.len() + 1;
+
+// suite de Sobol en base 2 de dimension donnée
+#[derive(Debug, Clone)]
+pub struct SobolSequence {
+    pub dimension: usize,
+    directions: Vec<[u32; BITS]>, // nombres directeurs v_k = m_k / 2^k, par dimension
+}
+
+impl SobolSequence {
+    // la première dimension est la suite de van der Corput, les suivantes utilisent les
+    // polynômes primitifs et nombres directeurs initiaux tabulés par Joe et Kuo
+    pub fn new(dimension: usize) -> Result<Self, PcpdError> {
+        if dimension == 0 {
+            return Err(PcpdError::Model(
+                "Sobol sequence dimension must be positive".to_string(),
+            ));
+        }
+        if dimension > SOBOL_MAX_DIMENSION {
+            return Err(PcpdError::Model(format!(
+                "Sobol sequence supports at most {} dimensions, {} requested",
+                SOBOL_MAX_DIMENSION, dimension
+            )));
+        }
+
+        let mut directions = Vec::with_capacity(dimension);
+        directions.push(direction_numbers(&[], 0, 0));
+        for &(degree, coefficients, initial) in &JOE_KUO[..dimension - 1] {
+            directions.push(direction_numbers(initial, degree, coefficients));
+        }
+
+        Ok(SobolSequence {
+            dimension,
+            directions,
+        })
+    }
+
+    // point d'indice n (ordre de Gray), en entiers sur 32 bits
+    pub fn point(&self, n: u64) -> Vec<u32> {
+        let gray = n ^ (n >> 1);
+
+        self.directions
+            .iter()
+            .map(|v| {
+                (0..BITS)
+                    .filter(|&k| gray & (1 << k) != 0)
+                    .fold(0, |x, k| x ^ v[k])
+            })
+            .collect()
+    }
+}
+
+// source de gaussiennes quasi-aléatoires : suite de Sobol décalée digitalement (XOR avec un
+// vecteur aléatoire), ce qui la rend sans biais tout en gardant sa structure ; chaque appel à
+// normal_vec renvoie les premières coordonnées du point suivant
+#[derive(Debug, Clone)]
+pub struct SobolSource {
+    sequence: Arc<SobolSequence>,
+    shift: Vec<u32>,
+    index: u64,        // indice du prochain point
+    current: Vec<u32>, // point d'indice index - 1
+}
+
+impl SobolSource {
+    // source pour simuler le modèle depuis t (ou toute date ultérieure) : chaque trajectoire
+    // consomme model.gaussians_number(t) coordonnées d'un même point
+    pub fn new<M: Model + ?Sized, R: RngCore + ?Sized>(
+        sequence: Arc<SobolSequence>,
+        model: &M,
+        t: f64,
+        rng: &mut R,
+    ) -> Result<Self, PcpdError> {
+        let gaussians = model.gaussians_number(t);
+        if gaussians > sequence.dimension {
+            return Err(PcpdError::Model(format!(
+                "Sobol sequence of dimension {} cannot provide {} gaussians per path",
+                sequence.dimension, gaussians
+            )));
+        }
+
+        let shift = (0..sequence.dimension).map(|_| rng.next_u32()).collect();
+        Ok(SobolSource::starting_at(sequence, shift, 0))
+    }
+
+    fn starting_at(sequence: Arc<SobolSequence>, shift: Vec<u32>, index: u64) -> Self {
+        let current = match index {
+            0 => vec![0; sequence.dimension],
+            _ => sequence.point(index - 1),
+        };
+
+        SobolSource {
+            sequence,
+            shift,
+            index,
+            current,
+        }
+    }
+
+    // point suivant dans [0, 1[^dimension, avant passage à la loi normale
+    pub fn next_uniform(&mut self) -> Vec<f64> {
+        // x_n = x_{n-1} XOR v_c, c = position du bit qui change entre gray(n - 1) et gray(n)
+        if self.index > 0 {
+            let c = (self.index - 1).trailing_ones() as usize;
+            for (x, v) in self.current.iter_mut().zip(&self.sequence.directions) {
+                *x ^= v[c];
+            }
+        }
+        self.index += 1;
+
+        self.current
+            .iter()
+            .zip(&self.shift)
+            .map(|(&x, &s)| ((x ^ s) as f64 + 0.5) / 2f64.powi(BITS as i32))
+            .collect()
+    }
+}
+
+impl RandomSource for SobolSource {
+    fn normal_vec(&mut self, n: usize) -> Vec<f64> {
+        // invariant : new vérifie gaussians_number pour les simulations depuis t
+        assert!(
+            n <= self.sequence.dimension,
+            "Sobol sequence of dimension {} cannot provide {} gaussians per path!",
+            self.sequence.dimension,
+            n
+        );
+
+        self.next_uniform()
+            .into_iter()
+            .take(n)
+            .map(normal_inverse_cdf)
+            .collect()
+    }
+}
+
+impl SplittableSource for SobolSource {
+    // le paquet k reçoit les points [index + k * chunk_size, index + (k + 1) * chunk_size[
+    fn split(&mut self, chunks: usize, chunk_size: usize) -> Vec<Self> {
+        let sources = (0..chunks)
+            .map(|k| {
+                let start = self.index + (k * chunk_size) as u64;
+                SobolSource::starting_at(self.sequence.clone(), self.shift.clone(), start)
+            })
+            .collect();
+
+        *self = SobolSource::starting_at(
+            self.sequence.clone(),
+            self.shift.clone(),
+            self.index + (chunks * chunk_size) as u64,
+        );
+
+        sources
+    }
+}
+
+// nombres directeurs v_1, ..., v_32 de la récurrence
+// m_k = 2 a_1 m_{k-1} ^ 4 a_2 m_{k-2} ^ ... ^ 2^{s-1} a_{s-1} m_{k-s+1} ^ 2^s m_{k-s} ^ m_{k-s}
+fn direction_numbers(initial: &[u32], degree: u32, coefficients: u32) -> [u32; BITS] {
+    let s = degree as usize;
+    let mut m = [1u64; BITS + 1]; // m[k] pour k = 1..=32, m[0] inutilisé
+
+    for k in 1..=BITS {
+        if k <= s {
+            m[k] = initial[k - 1] as u64;
+        } else if s > 0 {
+            let mut value = m[k - s] ^ (m[k - s] << s);
+            for i in 1..s {
+                // a_i est le bit s - 1 - i de coefficients
+                if (coefficients >> (s - 1 - i)) & 1 == 1 {
+                    value ^= m[k - i] << i;
+                }
+            }
+            m[k] = value;
+        }
+    }
+
+    let mut v = [0u32; BITS];
+    for k in 1..=BITS {
+        v[k - 1] = (m[k] << (BITS - k)) as u32;
+    }
+    v
+}
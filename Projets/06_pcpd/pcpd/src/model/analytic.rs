@@ -0,0 +1,392 @@
+use crate::config::PricingConfig;
+use crate::error::PcpdError;
+use crate::math::random::{normal_cdf, normal_pdf};
+use crate::options::barrier::{BarrierDirection, BarrierKnock, BarrierOption};
+
+// troncature de la série de Merton
+const MERTON_MAX_JUMPS: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VanillaKind {
+    Call,
+    Put,
+}
+
+// prix et sensibilités d'une option vanille, en t = 0 ; theta est la dérivée par rapport
+// au temps calendaire (-dV/dT), vega et rho sont pour une variation de 1 (et non de 1%)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Greeks {
+    pub price: f64,
+    pub delta: f64,
+    pub gamma: f64,
+    pub vega: f64,
+    pub theta: f64,
+    pub rho: f64,
+}
+
+// formules fermées de Black-Scholes pour quantity * (S_T - K)_+ ou quantity * (K - S_T)_+,
+// avec un rendement continu de dividende q
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnalyticVanilla {
+    pub kind: VanillaKind,
+    pub spot: f64,           // S_0
+    pub strike: f64,         // K
+    pub maturity: f64,       // T
+    pub interest_rate: f64,  // r
+    pub dividend_yield: f64, // q
+    pub volatility: f64,     // sigma
+    pub quantity: f64,       // nombre de sous-jacents (coefficient d'un panier à un actif)
+}
+
+impl AnalyticVanilla {
+    pub fn new(
+        kind: VanillaKind,
+        spot: f64,
+        strike: f64,
+        maturity: f64,
+        interest_rate: f64,
+        dividend_yield: f64,
+        volatility: f64,
+    ) -> Self {
+        AnalyticVanilla {
+            kind,
+            spot,
+            strike,
+            maturity,
+            interest_rate,
+            dividend_yield,
+            volatility,
+            quantity: 1.0,
+        }
+    }
+
+    // options "call" et "put", et panier à un seul actif (lambda S_T - K)_+
+    pub fn from_config(config: &PricingConfig) -> Result<Self, PcpdError> {
+        if config.option_size != 1 {
+            return Err(PcpdError::Config(format!(
+                "closed-form prices need a single asset, but \"option size\" is {}",
+                config.option_size
+            )));
+        }
+
+        let strike = config.strike.ok_or_else(|| {
+            PcpdError::Config("\"strike\" is required for closed-form prices".to_string())
+        })?;
+
+        let (kind, quantity) = match config.option_type.as_str() {
+            "call" => (VanillaKind::Call, 1.0),
+            "put" => (VanillaKind::Put, 1.0),
+            "basket" => match config.payoff_coefficients.first() {
+                Some(&lambda) if lambda > 0.0 => (VanillaKind::Call, lambda),
+                _ => {
+                    return Err(PcpdError::Config(
+                        "closed-form basket price needs a positive payoff coefficient".to_string(),
+                    ));
+                }
+            },
+            other => {
+                return Err(PcpdError::Config(format!(
+                    "no closed-form price for option type \"{}\"",
+                    other
+                )));
+            }
+        };
+
+        let mut vanilla = AnalyticVanilla::new(
+            kind,
+            config.spot[0],
+            strike,
+            config.maturity,
+            config.interest_rate,
+            config.dividend_rate.first().copied().unwrap_or(0.0),
+            config.volatility[0],
+        );
+        vanilla.quantity = quantity;
+        Ok(vanilla)
+    }
+
+    pub fn price(&self) -> f64 {
+        self.greeks().price
+    }
+
+    pub fn delta(&self) -> f64 {
+        self.greeks().delta
+    }
+
+    pub fn greeks(&self) -> Greeks {
+        // lambda (S - K)_+ = lambda (S - K / lambda)_+ : toutes les grecques sont multipliées par lambda
+        let lambda = self.quantity;
+        let greeks = self.unit_greeks(self.strike / lambda);
+
+        Greeks {
+            price: lambda * greeks.price,
+            delta: lambda * greeks.delta,
+            gamma: lambda * greeks.gamma,
+            vega: lambda * greeks.vega,
+            theta: lambda * greeks.theta,
+            rho: lambda * greeks.rho,
+        }
+    }
+
+    // prix dans le modèle de Merton : sachant n sauts log-normaux (log J ~ N(m, delta^2)) sur
+    // [0, T], le prix est celui de Black-Scholes avec sigma_n^2 = sigma^2 + n delta^2 / T et
+    // r_n = r - lambda k + n log(1 + k) / T, où k = E[J] - 1 ; n suit une loi de Poisson
+    // de paramètre lambda (1 + k) T
+    pub fn merton_price(&self, jump_intensity: f64, jump_mean: f64, jump_volatility: f64) -> f64 {
+        let t = self.maturity;
+        if t <= 0.0 || jump_intensity <= 0.0 {
+            return self.price();
+        }
+
+        let k = (jump_mean + 0.5 * jump_volatility * jump_volatility).exp_m1();
+        let poisson_mean = jump_intensity * (1.0 + k) * t;
+
+        let mut price = 0.0;
+        let mut weight = (-poisson_mean).exp();
+        for n in 0..MERTON_MAX_JUMPS {
+            let n_jumps = n as f64;
+            let vanilla = AnalyticVanilla {
+                interest_rate: self.interest_rate - jump_intensity * k + n_jumps * k.ln_1p() / t,
+                volatility: (self.volatility * self.volatility
+                    + n_jumps * jump_volatility * jump_volatility / t)
+                    .sqrt(),
+                ..*self
+            };
+            price += weight * vanilla.price();
+
+            weight *= poisson_mean / (n_jumps + 1.0);
+            if n_jumps > poisson_mean && weight < 1e-16 {
+                break;
+            }
+        }
+
+        price
+    }
+
+    fn unit_greeks(&self, k: f64) -> Greeks {
+        let s = self.spot;
+        let t = self.maturity;
+        let r = self.interest_rate;
+        let q = self.dividend_yield;
+        let sigma = self.volatility;
+
+        let dividend_discount = (-q * t).exp();
+        let discount = (-r * t).exp();
+        let forward = s * dividend_discount;
+        let discounted_strike = k * discount;
+
+        // variance nulle : le payoff est déterministe, égal à sa valeur forward
+        if t <= 0.0 || sigma <= 0.0 {
+            let sign = match self.kind {
+                VanillaKind::Call => 1.0,
+                VanillaKind::Put => -1.0,
+            };
+            let in_the_money = if sign * (forward - discounted_strike) > 0.0 {
+                1.0
+            } else {
+                0.0
+            };
+
+            return Greeks {
+                price: (sign * (forward - discounted_strike)).max(0.0),
+                delta: in_the_money * sign * dividend_discount,
+                gamma: 0.0,
+                vega: 0.0,
+                theta: in_the_money * sign * (q * forward - r * discounted_strike),
+                rho: in_the_money * sign * t * discounted_strike,
+            };
+        }
+
+        let sqrt_t = t.sqrt();
+        let d1 = ((s / k).ln() + (r - q + 0.5 * sigma * sigma) * t) / (sigma * sqrt_t);
+        let d2 = d1 - sigma * sqrt_t;
+
+        // termes communs au call et au put
+        let gamma = dividend_discount * normal_pdf(d1) / (s * sigma * sqrt_t);
+        let vega = forward * normal_pdf(d1) * sqrt_t;
+        let time_decay = -forward * normal_pdf(d1) * sigma / (2.0 * sqrt_t);
+
+        match self.kind {
+            VanillaKind::Call => Greeks {
+                price: forward * normal_cdf(d1) - discounted_strike * normal_cdf(d2),
+                delta: dividend_discount * normal_cdf(d1),
+                gamma,
+                vega,
+                theta: time_decay - r * discounted_strike * normal_cdf(d2)
+                    + q * forward * normal_cdf(d1),
+                rho: t * discounted_strike * normal_cdf(d2),
+            },
+            VanillaKind::Put => Greeks {
+                price: discounted_strike * normal_cdf(-d2) - forward * normal_cdf(-d1),
+                delta: -dividend_discount * normal_cdf(-d1),
+                gamma,
+                vega,
+                theta: time_decay + r * discounted_strike * normal_cdf(-d2)
+                    - q * forward * normal_cdf(-d1),
+                rho: -t * discounted_strike * normal_cdf(-d2),
+            },
+        }
+    }
+}
+
+// option barrière à surveillance continue (Reiner-Rubinstein) : vanille activée ("in") ou
+// désactivée ("out") quand quantity * S franchit la barrière, rebate payé en T si la
+// vanille n'est pas payée
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnalyticBarrier {
+    pub vanilla: AnalyticVanilla,
+    pub barrier: f64, // H, sur le niveau quantity * S
+    pub direction: BarrierDirection,
+    pub knock: BarrierKnock,
+    pub rebate: f64,
+}
+
+impl AnalyticBarrier {
+    pub fn new(
+        vanilla: AnalyticVanilla,
+        barrier: f64,
+        direction: BarrierDirection,
+        knock: BarrierKnock,
+        rebate: f64,
+    ) -> Self {
+        AnalyticBarrier {
+            vanilla,
+            barrier,
+            direction,
+            knock,
+            rebate,
+        }
+    }
+
+    // option "barrier" sur un seul actif
+    pub fn from_config(config: &PricingConfig) -> Result<Self, PcpdError> {
+        if config.option_type != "barrier" {
+            return Err(PcpdError::Config(format!(
+                "no closed-form barrier price for option type \"{}\"",
+                config.option_type
+            )));
+        }
+
+        if config.option_size != 1 {
+            return Err(PcpdError::Config(format!(
+                "closed-form prices need a single asset, but \"option size\" is {}",
+                config.option_size
+            )));
+        }
+
+        let barrier = BarrierOption::from_config(config)?;
+        let mut vanilla = AnalyticVanilla::new(
+            barrier.kind,
+            config.spot[0],
+            barrier.strike,
+            config.maturity,
+            config.interest_rate,
+            config.dividend_rate.first().copied().unwrap_or(0.0),
+            config.volatility[0],
+        );
+        vanilla.quantity = barrier.payoff_coefficients[0];
+        if vanilla.quantity <= 0.0 {
+            return Err(PcpdError::Config(
+                "closed-form barrier price needs a positive payoff coefficient".to_string(),
+            ));
+        }
+
+        Ok(AnalyticBarrier::new(
+            vanilla,
+            barrier.barrier,
+            barrier.direction,
+            barrier.knock,
+            barrier.rebate,
+        ))
+    }
+
+    pub fn price(&self) -> f64 {
+        let vanilla = self.vanilla;
+        let lambda = vanilla.quantity;
+        let discount = (-vanilla.interest_rate * vanilla.maturity).exp();
+
+        // lambda (S - K)_+ = lambda (S - K / lambda)_+, barrière H / lambda sur S
+        let unit = AnalyticVanilla {
+            strike: vanilla.strike / lambda,
+            quantity: 1.0,
+            ..vanilla
+        };
+        let h = self.barrier / lambda;
+        let vanilla_price = lambda * unit.price();
+
+        // barrière déjà franchie en t = 0
+        let crossed = match self.direction {
+            BarrierDirection::Up => unit.spot >= h,
+            BarrierDirection::Down => unit.spot <= h,
+        };
+        let (knocked_in, no_hit) = if crossed {
+            (vanilla_price, 0.0)
+        } else {
+            let (knocked_in, no_hit) = unit_knock_in(&unit, h, self.direction);
+            (lambda * knocked_in, no_hit)
+        };
+
+        match self.knock {
+            BarrierKnock::In => knocked_in + self.rebate * discount * no_hit,
+            BarrierKnock::Out => {
+                vanilla_price - knocked_in + self.rebate * discount * (1.0 - no_hit)
+            }
+        }
+    }
+}
+
+// prix de la vanille activée à la barrière h (sans rebate) et probabilité risque-neutre de ne
+// pas toucher h avant T, notations de Haug (The Complete Guide to Option Pricing Formulas)
+fn unit_knock_in(vanilla: &AnalyticVanilla, h: f64, direction: BarrierDirection) -> (f64, f64) {
+    let s = vanilla.spot;
+    let k = vanilla.strike;
+    let t = vanilla.maturity;
+    let r = vanilla.interest_rate;
+    let q = vanilla.dividend_yield;
+    let sigma = vanilla.volatility;
+
+    let eta = match direction {
+        BarrierDirection::Down => 1.0,
+        BarrierDirection::Up => -1.0,
+    };
+    let phi = match vanilla.kind {
+        VanillaKind::Call => 1.0,
+        VanillaKind::Put => -1.0,
+    };
+
+    let sigma_t = sigma * t.sqrt();
+    let mu = (r - q - 0.5 * sigma * sigma) / (sigma * sigma);
+    let x1 = (s / k).ln() / sigma_t + (1.0 + mu) * sigma_t;
+    let x2 = (s / h).ln() / sigma_t + (1.0 + mu) * sigma_t;
+    let y1 = (h * h / (s * k)).ln() / sigma_t + (1.0 + mu) * sigma_t;
+    let y2 = (h / s).ln() / sigma_t + (1.0 + mu) * sigma_t;
+
+    let forward = s * (-q * t).exp();
+    let discounted_strike = k * (-r * t).exp();
+    let ratio = h / s;
+
+    let a = phi * forward * normal_cdf(phi * x1)
+        - phi * discounted_strike * normal_cdf(phi * x1 - phi * sigma_t);
+    let b = phi * forward * normal_cdf(phi * x2)
+        - phi * discounted_strike * normal_cdf(phi * x2 - phi * sigma_t);
+    let c = phi * forward * ratio.powf(2.0 * (mu + 1.0)) * normal_cdf(eta * y1)
+        - phi * discounted_strike * ratio.powf(2.0 * mu) * normal_cdf(eta * y1 - eta * sigma_t);
+    let d = phi * forward * ratio.powf(2.0 * (mu + 1.0)) * normal_cdf(eta * y2)
+        - phi * discounted_strike * ratio.powf(2.0 * mu) * normal_cdf(eta * y2 - eta * sigma_t);
+
+    let above_barrier = k > h;
+    let knocked_in = match (direction, vanilla.kind, above_barrier) {
+        (BarrierDirection::Down, VanillaKind::Call, true) => c,
+        (BarrierDirection::Down, VanillaKind::Call, false) => a - b + d,
+        (BarrierDirection::Up, VanillaKind::Call, true) => a,
+        (BarrierDirection::Up, VanillaKind::Call, false) => b - c + d,
+        (BarrierDirection::Down, VanillaKind::Put, true) => b - c + d,
+        (BarrierDirection::Down, VanillaKind::Put, false) => a,
+        (BarrierDirection::Up, VanillaKind::Put, true) => a - b + d,
+        (BarrierDirection::Up, VanillaKind::Put, false) => c,
+    };
+    let no_hit = normal_cdf(eta * x2 - eta * sigma_t)
+        - ratio.powf(2.0 * mu) * normal_cdf(eta * y2 - eta * sigma_t);
+
+    (knocked_in, no_hit)
+}
@@ -1,115 +1,331 @@
-use crate::math::random::normal_vec;
-use ndarray::{Array1, Array2};
-use rand::rngs::ThreadRng;
+use std::collections::VecDeque;
+
+use crate::config::PricingConfig;
+use crate::error::PcpdError;
+use crate::mc::rng::RandomSource;
+use crate::model::dynamics::{Model, correlation_root, dividend_rate_from};
+use ndarray::{Array1, Array2, ArrayView1, Axis, s};
 use serde_json::Value;
 
 pub struct BlackScholesModel {
-    pub model_size: usize,           // nombre d'actifs du modèle
-    pub interest_rate: f64,          // taux d'intérêt
-    pub correlation: f64,            // paramètre de corrélation
-    pub volatility: Array1<f64>,     // vecteur de volatilités
-    pub spots: Array1<f64>,          // valeurs initiales des sous-jacents
-    pub l: Array2<f64>,              // racine carrée de matrice de corrélation
-    pub fixings_dates_number: usize, // fixings date Number N
-    pub time_step: f64,              // time step = T / N
+    pub model_size: usize,                   // nombre d'actifs du modèle
+    pub maturity: f64,                       // maturité T
+    pub interest_rate: f64,                  // taux d'intérêt
+    pub dividend_rate: Array1<f64>,          // taux de dividende continu par actif
+    pub correlation: f64,                    // paramètre de corrélation
+    pub volatility: Array1<f64>,             // vecteur de volatilités
+    pub spots: Array1<f64>,                  // valeurs initiales des sous-jacents
+    pub l: Array2<f64>,                      // racine carrée de matrice de corrélation
+    pub fixings_dates_number: usize,         // fixings date Number N
+    pub time_step: f64,                      // time step = T / N
+    pub path_construction: PathConstruction, // construction des trajectoires browniennes
+}
+
+// ordre dans lequel les gaussiennes d'une trajectoire sont utilisées
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PathConstruction {
+    // pas à pas : la k-ième gaussienne donne le k-ième accroissement
+    #[default]
+    Incremental,
+    // pont brownien : les premières gaussiennes fixent la valeur finale puis les points milieux,
+    // ce qui concentre la variance sur les premières coordonnées (utile en quasi-Monte Carlo)
+    BrownianBridge,
 }
 
 impl BlackScholesModel {
     pub fn new() -> Self {
         BlackScholesModel {
             model_size: 0,
+            maturity: 0.0,
             interest_rate: 0.0,
+            dividend_rate: Array1::zeros(0),
             correlation: 0.0,
             volatility: Array1::zeros(0),
             spots: Array1::zeros(0),
             l: Array2::zeros((0, 0)),
             fixings_dates_number: 1,
             time_step: 0.0,
+            path_construction: PathConstruction::Incremental,
         }
     }
 }
 
+impl Default for BlackScholesModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl BlackScholesModel {
-    pub fn from_json(json: &Value) -> Self {
-        let t = json["maturity"].as_f64().unwrap();
-        let n = json["fixing dates number"].as_f64().unwrap();
-        let time_step = t / n;
-
-        let model_size = json["option size"].as_u64().unwrap() as usize;
-        let interest_rate = json["interest rate"].as_f64().unwrap();
-        let correlation = json["correlation"].as_f64().unwrap();
-
-        // Volatility
-        let volatility: Array1<f64> = {
-            let arr = json["volatility"].as_array().unwrap();
-            let mut v: Vec<f64> = arr.iter().map(|x| x.as_f64().unwrap()).collect();
-            if v.len() == 1 && model_size > 1 {
-                v = vec![v[0]; model_size];
-            }
-            Array1::from(v)
-        };
-
-        // Spots
-        let spots: Array1<f64> = {
-            let arr = json["spot"].as_array().unwrap();
-            let mut v: Vec<f64> = arr.iter().map(|x| x.as_f64().unwrap()).collect();
-            if v.len() == 1 && model_size > 1 {
-                v = vec![v[0]; model_size];
-            }
-            Array1::from(v)
-        };
+    pub fn from_json(json: &Value) -> Result<Self, PcpdError> {
+        let config = PricingConfig::from_json(json)?;
+        Self::from_config(&config)
+    }
 
-        // Matrice de corrélation
-        let mut l = Array2::<f64>::from_elem((model_size, model_size), correlation);
-        for i in 0..model_size {
-            l[(i, i)] = 1.0;
-        }
-        // À remplacer par une vraie décomposition de Cholesky si besoin
-        // l = cholesky(&l);
+    pub fn from_config(config: &PricingConfig) -> Result<Self, PcpdError> {
+        let t = config.maturity;
+        let n = config.fixing_dates_number;
+        let time_step = t / n as f64;
 
-        BlackScholesModel {
+        let model_size = config.option_size;
+        let interest_rate = config.interest_rate;
+        let correlation = config.correlation;
+
+        let volatility = Array1::from(config.volatility.clone());
+        let dividend_rate = dividend_rate_from(&config.dividend_rate, model_size);
+        let spots = Array1::from(config.spot.clone());
+
+        let l = correlation_root(correlation, model_size)?;
+
+        Ok(BlackScholesModel {
             model_size,
+            maturity: t,
             interest_rate,
+            dividend_rate,
             correlation,
             volatility,
             spots,
             l,
-            fixings_dates_number: n as usize,
+            fixings_dates_number: n,
             time_step,
-        }
+            path_construction: PathConstruction::Incremental,
+        })
     }
 }
 
 impl BlackScholesModel {
-    pub fn asset(&self, rng: &mut ThreadRng) -> Array2<f64> {
+    pub fn asset<R: RandomSource + ?Sized>(&self, rng: &mut R) -> Result<Array2<f64>, PcpdError> {
+        // en t = 0, le passé se réduit aux spots
+        let past = self.spots.clone().insert_axis(Axis(0));
+        self.asset_from(&past, 0.0, rng)
+    }
+
+    // past contient S_{t_0}, ..., S_{t_i} puis S_t si t n'est pas une date de fixing
+    pub fn asset_from<R: RandomSource + ?Sized>(
+        &self,
+        past: &Array2<f64>,
+        t: f64,
+        rng: &mut R,
+    ) -> Result<Array2<f64>, PcpdError> {
         let d = self.model_size;
         let r = self.interest_rate;
         let n = self.fixings_dates_number;
+        let last_index = self.last_fixing_index(t);
+
+        if past.ncols() != d {
+            return Err(PcpdError::Model(format!(
+                "past has {} columns but model size is {}",
+                past.ncols(),
+                d
+            )));
+        }
+        if past.nrows() < last_index + 1 {
+            return Err(PcpdError::Model(format!(
+                "past has {} rows but {} fixing dates are observed at t = {}",
+                past.nrows(),
+                last_index + 1,
+                t
+            )));
+        }
+
+        // N + 1 lignes : t_0 = 0, t_1, ..., t_N = T
+        let mut path = Array2::<f64>::from_elem((n + 1, d), 0.);
 
-        let mut path = Array2::<f64>::from_elem((n, d), 0.);
+        // Dates de fixing déjà observées
+        path.slice_mut(s![..=last_index, ..])
+            .assign(&past.slice(s![..=last_index, ..]));
 
-        // Condition initiale
-        path.row_mut(0).assign(&Array1::from(self.spots.clone()));
+        // Pas de temps restants entre t et T
+        let dts: Vec<f64> = ((last_index + 1)..=n)
+            .map(|i| i as f64 * self.time_step - t.max((i - 1) as f64 * self.time_step))
+            .collect();
+        let increments = self.gaussian_increments(&dts, rng);
 
-        for i in 1..n {
-            // vecteur Gaussien i.i.d
-            let g = Array1::from(normal_vec(d, rng, 0.0, 1.0));
+        // Simulation à partir de S_t
+        let mut current = past.row(past.nrows() - 1).to_owned();
+
+        for (k, i) in ((last_index + 1)..=n).enumerate() {
+            let dt = dts[k];
 
             // vecteur corrélé z = L * g
-            let z = self.l.dot(&g);
+            let z = self.l.dot(&increments.row(k));
 
             for j in 0..d {
                 let sigma = self.volatility[j];
 
-                let drift = (r - 0.5 * sigma * sigma) * self.time_step;
-                let diffusion = sigma * self.time_step.sqrt() * z[j];
+                let drift = (r - self.dividend_rate[j] - 0.5 * sigma * sigma) * dt;
+                let diffusion = sigma * dt.sqrt() * z[j];
+
+                current[j] *= (drift + diffusion).exp();
+            }
+
+            path.row_mut(i).assign(&current);
+        }
+
+        Ok(path)
+    }
+
+    // accroissements browniens normalisés (W_{k+1} - W_k) / sqrt(dt_k), i.i.d N(0, 1) par actif ;
+    // toutes les gaussiennes d'une trajectoire sont tirées en un seul appel à la source
+    fn gaussian_increments<R: RandomSource + ?Sized>(
+        &self,
+        dts: &[f64],
+        rng: &mut R,
+    ) -> Array2<f64> {
+        let d = self.model_size;
+        let steps = dts.len();
+        if steps == 0 {
+            return Array2::zeros((0, d));
+        }
 
-                let facteur = (drift + diffusion).exp();
+        let g = Array2::from_shape_vec((steps, d), rng.normal_vec(steps * d)).unwrap();
 
-                path[[i, j]] = path[[i - 1, j]] * facteur;
+        match self.path_construction {
+            PathConstruction::Incremental => g,
+            PathConstruction::BrownianBridge => {
+                let mut increments = Array2::<f64>::zeros((steps, d));
+                for j in 0..d {
+                    let w = brownian_bridge(dts, &g.column(j).to_vec());
+                    let mut previous = 0.0;
+                    for k in 0..steps {
+                        increments[[k, j]] = (w[k] - previous) / dts[k].sqrt();
+                        previous = w[k];
+                    }
+                }
+                increments
             }
         }
+    }
+
+    // trajectoire de marché sous la probabilité historique : dérive trend au lieu du taux
+    // d'intérêt, observée aux dates_number + 1 dates i * T / dates_number
+    pub fn simulate_market<R: RandomSource + ?Sized>(
+        &self,
+        trend: &Array1<f64>,
+        dates_number: usize,
+        rng: &mut R,
+    ) -> Result<Array2<f64>, PcpdError> {
+        let d = self.model_size;
+
+        if trend.len() != d {
+            return Err(PcpdError::Model(format!(
+                "trend has {} values but model size is {}",
+                trend.len(),
+                d
+            )));
+        }
+        if dates_number == 0 {
+            return Err(PcpdError::Model(
+                "a market path needs at least one date after t = 0".to_string(),
+            ));
+        }
+
+        let dt = self.maturity / dates_number as f64;
+        let increments = self.gaussian_increments(&vec![dt; dates_number], rng);
+
+        let mut market = Array2::<f64>::zeros((dates_number + 1, d));
+        market.row_mut(0).assign(&self.spots);
 
-        path
+        let mut current = self.spots.clone();
+        for k in 0..dates_number {
+            let z = self.l.dot(&increments.row(k));
+
+            for j in 0..d {
+                let sigma = self.volatility[j];
+
+                let drift = (trend[j] - self.dividend_rate[j] - 0.5 * sigma * sigma) * dt;
+                let diffusion = sigma * dt.sqrt() * z[j];
+
+                current[j] *= (drift + diffusion).exp();
+            }
+
+            market.row_mut(k + 1).assign(&current);
+        }
+
+        Ok(market)
+    }
+}
+
+impl Model for BlackScholesModel {
+    fn model_size(&self) -> usize {
+        self.model_size
+    }
+
+    fn maturity(&self) -> f64 {
+        self.maturity
+    }
+
+    fn fixings_dates_number(&self) -> usize {
+        self.fixings_dates_number
+    }
+
+    fn interest_rate(&self) -> f64 {
+        self.interest_rate
+    }
+
+    fn dividend_rate(&self) -> ArrayView1<'_, f64> {
+        self.dividend_rate.view()
+    }
+
+    fn spots(&self) -> ArrayView1<'_, f64> {
+        self.spots.view()
     }
+
+    fn asset_from(
+        &self,
+        past: &Array2<f64>,
+        t: f64,
+        rng: &mut dyn RandomSource,
+    ) -> Result<Array2<f64>, PcpdError> {
+        BlackScholesModel::asset_from(self, past, t, rng)
+    }
+
+    fn set_path_construction(&mut self, construction: PathConstruction) {
+        self.path_construction = construction;
+    }
+
+    fn as_black_scholes(&self) -> Option<&BlackScholesModel> {
+        Some(self)
+    }
+
+    fn time_step(&self) -> f64 {
+        self.time_step
+    }
+}
+
+// valeurs W(tau_1), ..., W(tau_m) d'un brownien construit par pont : g[0] donne W(tau_m),
+// puis chaque gaussienne suivante fixe le point milieu d'un intervalle déjà encadré
+fn brownian_bridge(dts: &[f64], g: &[f64]) -> Vec<f64> {
+    let m = dts.len();
+
+    // temps cumulés tau_0 = 0, tau_1, ..., tau_m
+    let mut times = vec![0.0; m + 1];
+    for k in 0..m {
+        times[k + 1] = times[k] + dts[k];
+    }
+
+    // w[k] = W(tau_k), w[0] = 0
+    let mut w = vec![0.0; m + 1];
+    w[m] = times[m].sqrt() * g[0];
+
+    let mut next = 1;
+    let mut intervals = VecDeque::from([(0, m)]);
+    while let Some((left, right)) = intervals.pop_front() {
+        if right - left < 2 {
+            continue;
+        }
+
+        let mid = (left + right) / 2;
+        let (t_l, t_m, t_r) = (times[left], times[mid], times[right]);
+
+        let mean = ((t_r - t_m) * w[left] + (t_m - t_l) * w[right]) / (t_r - t_l);
+        let std_dev = ((t_m - t_l) * (t_r - t_m) / (t_r - t_l)).sqrt();
+        w[mid] = mean + std_dev * g[next];
+        next += 1;
+
+        intervals.push_back((left, mid));
+        intervals.push_back((mid, right));
+    }
+
+    w[1..].to_vec()
 }
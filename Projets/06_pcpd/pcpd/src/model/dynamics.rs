@@ -0,0 +1,229 @@
+use ndarray::{Array1, Array2, ArrayView1, Axis, s};
+
+use crate::error::PcpdError;
+use crate::math::linalg::cholesky;
+use crate::mc::rng::RandomSource;
+use crate::model::black_scholes::{BlackScholesModel, PathConstruction};
+
+// tolérance pour décider si t est une date de fixing (ou de couverture)
+pub(crate) const TIME_EPSILON: f64 = 1e-9;
+
+// dynamique des sous-jacents : les trajectoires ont N + 1 lignes (t_0 = 0, ..., t_N = T)
+// et une colonne par actif ; Send + Sync : le pricer simule depuis plusieurs threads
+pub trait Model: Send + Sync {
+    fn model_size(&self) -> usize;
+
+    fn maturity(&self) -> f64;
+
+    fn fixings_dates_number(&self) -> usize;
+
+    // taux sans risque, utilisé pour l'actualisation et le compte cash de la couverture
+    fn interest_rate(&self) -> f64;
+
+    // taux de dividende continu par actif
+    fn dividend_rate(&self) -> ArrayView1<'_, f64>;
+
+    fn spots(&self) -> ArrayView1<'_, f64>;
+
+    // trajectoire sur [t, T] sachant le passé : S_{t_0}, ..., S_{t_i} puis S_t si t n'est pas
+    // une date de fixing ; toutes les gaussiennes de la trajectoire sont tirées en un seul appel
+    // rng.normal_vec(gaussians_number(t)), dont dépendent les paires antithétiques et les points
+    // quasi-Monte Carlo
+    fn asset_from(
+        &self,
+        past: &Array2<f64>,
+        t: f64,
+        rng: &mut dyn RandomSource,
+    ) -> Result<Array2<f64>, PcpdError>;
+
+    fn asset(&self, rng: &mut dyn RandomSource) -> Result<Array2<f64>, PcpdError> {
+        // en t = 0, le passé se réduit aux spots
+        let past = self.spots().to_owned().insert_axis(Axis(0));
+        self.asset_from(&past, 0.0, rng)
+    }
+
+    // nombre de gaussiennes tirées (en un seul appel à la source) par une trajectoire simulée
+    // à partir de t : dimension des points quasi-Monte Carlo
+    fn gaussians_number(&self, t: f64) -> usize {
+        (self.fixings_dates_number() - self.last_fixing_index(t)) * self.model_size()
+    }
+
+    // ordre d'utilisation des gaussiennes, ignoré par les modèles qui ne le gèrent pas
+    fn set_path_construction(&mut self, _construction: PathConstruction) {}
+
+    // paramètres de Black-Scholes, pour les méthodes qui reposent sur des formules fermées
+    // (variables de contrôle, estimateurs des grecques)
+    fn as_black_scholes(&self) -> Option<&BlackScholesModel> {
+        None
+    }
+
+    fn time_step(&self) -> f64 {
+        self.maturity() / self.fixings_dates_number() as f64
+    }
+
+    // facteur d'actualisation de T à t
+    fn discount(&self, t: f64) -> f64 {
+        (-self.interest_rate() * (self.maturity() - t)).exp()
+    }
+
+    // indice i de la dernière date de fixing t_i <= t
+    fn last_fixing_index(&self, t: f64) -> usize {
+        let i = (t / self.time_step() + TIME_EPSILON).floor() as usize;
+        i.min(self.fixings_dates_number())
+    }
+
+    // indice de la première date de fixing t_i >= t, i.e. la première affectée par S_t
+    fn first_shifted_index(&self, t: f64) -> usize {
+        let i = self.last_fixing_index(t);
+        if (t - i as f64 * self.time_step()).abs() < TIME_EPSILON {
+            i
+        } else {
+            i + 1
+        }
+    }
+
+    // multiplie par (1 + h) la composante d du sous-jacent aux dates t_i >= t ;
+    // les trajectoires sont proportionnelles à S_t, ce qui vaut pour tous les modèles du crate
+    fn shift_asset(&self, path: &Array2<f64>, d: usize, h: f64, t: f64) -> Array2<f64> {
+        let mut shifted = path.clone();
+        let first_index = self.first_shifted_index(t);
+
+        shifted
+            .slice_mut(s![first_index.., d])
+            .mapv_inplace(|x| x * (1.0 + h));
+
+        shifted
+    }
+}
+
+// modèle choisi à l'exécution par "model type"
+impl Model for Box<dyn Model> {
+    fn model_size(&self) -> usize {
+        (**self).model_size()
+    }
+
+    fn maturity(&self) -> f64 {
+        (**self).maturity()
+    }
+
+    fn fixings_dates_number(&self) -> usize {
+        (**self).fixings_dates_number()
+    }
+
+    fn interest_rate(&self) -> f64 {
+        (**self).interest_rate()
+    }
+
+    fn dividend_rate(&self) -> ArrayView1<'_, f64> {
+        (**self).dividend_rate()
+    }
+
+    fn spots(&self) -> ArrayView1<'_, f64> {
+        (**self).spots()
+    }
+
+    fn asset_from(
+        &self,
+        past: &Array2<f64>,
+        t: f64,
+        rng: &mut dyn RandomSource,
+    ) -> Result<Array2<f64>, PcpdError> {
+        (**self).asset_from(past, t, rng)
+    }
+
+    fn asset(&self, rng: &mut dyn RandomSource) -> Result<Array2<f64>, PcpdError> {
+        (**self).asset(rng)
+    }
+
+    fn gaussians_number(&self, t: f64) -> usize {
+        (**self).gaussians_number(t)
+    }
+
+    fn set_path_construction(&mut self, construction: PathConstruction) {
+        (**self).set_path_construction(construction)
+    }
+
+    fn as_black_scholes(&self) -> Option<&BlackScholesModel> {
+        (**self).as_black_scholes()
+    }
+
+    fn time_step(&self) -> f64 {
+        (**self).time_step()
+    }
+
+    fn discount(&self, t: f64) -> f64 {
+        (**self).discount(t)
+    }
+
+    fn last_fixing_index(&self, t: f64) -> usize {
+        (**self).last_fixing_index(t)
+    }
+
+    fn first_shifted_index(&self, t: f64) -> usize {
+        (**self).first_shifted_index(t)
+    }
+
+    fn shift_asset(&self, path: &Array2<f64>, d: usize, h: f64, t: f64) -> Array2<f64> {
+        (**self).shift_asset(path, d, h, t)
+    }
+}
+
+// dividendes nuls par défaut, un taux par actif sinon
+pub(crate) fn dividend_rate_from(values: &[f64], model_size: usize) -> Array1<f64> {
+    if values.is_empty() {
+        Array1::zeros(model_size)
+    } else {
+        Array1::from(values.to_vec())
+    }
+}
+
+// paramètre propre à un modèle, obligatoire dans la configuration
+pub(crate) fn required_parameter(
+    model: &str,
+    name: &str,
+    values: &[f64],
+) -> Result<Array1<f64>, PcpdError> {
+    if values.is_empty() {
+        return Err(PcpdError::Config(format!(
+            "\"{}\" is required for the {} model",
+            name, model
+        )));
+    }
+    Ok(Array1::from(values.to_vec()))
+}
+
+// le passé doit contenir les fixings t_0, ..., t_i observés en t pour chacun des d actifs
+pub(crate) fn check_past(
+    past: &Array2<f64>,
+    t: f64,
+    last_index: usize,
+    model_size: usize,
+) -> Result<(), PcpdError> {
+    if past.ncols() != model_size || past.nrows() < last_index + 1 {
+        return Err(PcpdError::Model(format!(
+            "past has shape {:?} but {} fixing dates of {} assets are observed at t = {}",
+            past.shape(),
+            last_index + 1,
+            model_size,
+            t
+        )));
+    }
+    Ok(())
+}
+
+// racine carrée L (Cholesky) de la matrice de corrélation uniforme entre les actifs
+pub(crate) fn correlation_root(
+    correlation: f64,
+    model_size: usize,
+) -> Result<Array2<f64>, PcpdError> {
+    let mut correlation_matrix = Array2::<f64>::from_elem((model_size, model_size), correlation);
+    for i in 0..model_size {
+        correlation_matrix[(i, i)] = 1.0;
+    }
+
+    cholesky(&correlation_matrix).map_err(|source| PcpdError::InvalidCorrelation {
+        correlation,
+        model_size,
+        source,
+    })
+}
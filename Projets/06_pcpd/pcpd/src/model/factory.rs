@@ -0,0 +1,25 @@
+use serde_json::Value;
+
+use crate::config::PricingConfig;
+use crate::error::PcpdError;
+use crate::model::black_scholes::BlackScholesModel;
+use crate::model::dynamics::Model;
+use crate::model::heston::HestonModel;
+use crate::model::merton::MertonModel;
+
+// construit le modèle décrit par le champ "model type"
+pub fn model_from_config(config: &PricingConfig) -> Result<Box<dyn Model>, PcpdError> {
+    let model: Box<dyn Model> = match config.model_type.as_str() {
+        "bs" => Box::new(BlackScholesModel::from_config(config)?),
+        "heston" => Box::new(HestonModel::from_config(config)?),
+        "merton" => Box::new(MertonModel::from_config(config)?),
+        other => return Err(PcpdError::UnknownModelType(other.to_string())),
+    };
+
+    Ok(model)
+}
+
+pub fn model_from_json(json: &Value) -> Result<Box<dyn Model>, PcpdError> {
+    let config = PricingConfig::from_json(json)?;
+    model_from_config(&config)
+}
@@ -0,0 +1,207 @@
+use ndarray::{Array1, Array2, ArrayView1, s};
+use serde_json::Value;
+
+use crate::config::PricingConfig;
+use crate::error::PcpdError;
+use crate::mc::rng::RandomSource;
+use crate::model::dynamics::{
+    Model, check_past, correlation_root, dividend_rate_from, required_parameter,
+};
+
+// nombre de pas du schéma d'Euler sur [0, T] si "euler steps" est absent
+const DEFAULT_EULER_STEPS: usize = 100;
+
+// modèle de Heston multi-actifs : pour chaque actif j,
+//   dS_j / S_j = (r - q_j) dt + sqrt(v_j) dW_j,
+//   dv_j = kappa_j (theta_j - v_j) dt + xi_j sqrt(v_j) dB_j,  d<W_j, B_j> = rho_j dt,
+// les W_j étant corrélés entre eux par "correlation" et les B_j indépendants conditionnellement aux W
+pub struct HestonModel {
+    pub model_size: usize,           // nombre d'actifs du modèle
+    pub maturity: f64,               // maturité T
+    pub interest_rate: f64,          // taux d'intérêt
+    pub dividend_rate: Array1<f64>,  // taux de dividende continu par actif
+    pub correlation: f64,            // corrélation entre les actifs
+    pub spots: Array1<f64>,          // valeurs initiales des sous-jacents
+    pub l: Array2<f64>,              // racine carrée de la matrice de corrélation des actifs
+    pub kappa: Array1<f64>,          // vitesses de retour à la moyenne
+    pub theta: Array1<f64>,          // variances de long terme
+    pub xi: Array1<f64>,             // volatilités des variances
+    pub v0: Array1<f64>,             // variances courantes
+    pub rho: Array1<f64>,            // corrélations actif / variance
+    pub fixings_dates_number: usize, // nombre de dates de fixing N
+    pub time_step: f64,              // T / N
+    pub euler_steps: usize,          // nombre de pas d'Euler sur [0, T]
+}
+
+impl HestonModel {
+    pub fn from_json(json: &Value) -> Result<Self, PcpdError> {
+        let config = PricingConfig::from_json(json)?;
+        Self::from_config(&config)
+    }
+
+    pub fn from_config(config: &PricingConfig) -> Result<Self, PcpdError> {
+        let d = config.option_size;
+        let kappa = required_parameter("heston", "kappa", &config.kappa)?;
+        let theta = required_parameter("heston", "theta", &config.theta)?;
+        let xi = required_parameter("heston", "xi", &config.xi)?;
+        // variance initiale par défaut : celle de Black-Scholes
+        let v0 = match config.v0.is_empty() {
+            true => Array1::from(config.volatility.clone()).mapv(|sigma| sigma * sigma),
+            false => Array1::from(config.v0.clone()),
+        };
+        let rho = match config.spot_vol_correlation.is_empty() {
+            true => Array1::zeros(d),
+            false => Array1::from(config.spot_vol_correlation.clone()),
+        };
+
+        if kappa.iter().any(|&x| x <= 0.0) {
+            return Err(PcpdError::Model("\"kappa\" must be positive".to_string()));
+        }
+        if theta.iter().chain(&xi).chain(&v0).any(|&x| x < 0.0) {
+            return Err(PcpdError::Model(
+                "\"theta\", \"xi\" and \"v0\" must be non-negative".to_string(),
+            ));
+        }
+        if rho.iter().any(|x| x.abs() > 1.0) {
+            return Err(PcpdError::Model(
+                "\"spot vol correlation\" must be in [-1, 1]".to_string(),
+            ));
+        }
+
+        let euler_steps = config.euler_steps.unwrap_or(DEFAULT_EULER_STEPS);
+        if euler_steps == 0 {
+            return Err(PcpdError::Config(
+                "\"euler steps\" must be positive".to_string(),
+            ));
+        }
+
+        let n = config.fixing_dates_number;
+        Ok(HestonModel {
+            model_size: d,
+            maturity: config.maturity,
+            interest_rate: config.interest_rate,
+            dividend_rate: dividend_rate_from(&config.dividend_rate, d),
+            correlation: config.correlation,
+            spots: Array1::from(config.spot.clone()),
+            l: correlation_root(config.correlation, d)?,
+            kappa,
+            theta,
+            xi,
+            v0,
+            rho,
+            fixings_dates_number: n,
+            time_step: config.maturity / n as f64,
+            euler_steps,
+        })
+    }
+}
+
+impl HestonModel {
+    // pas de temps restants de t à T : chaque intervalle entre deux dates de fixing
+    // est découpé en pas d'Euler de longueur au plus T / euler_steps
+    fn substeps(&self, t: f64) -> Vec<(usize, Vec<f64>)> {
+        let max_step = self.maturity / self.euler_steps as f64;
+
+        ((self.last_fixing_index(t) + 1)..=self.fixings_dates_number)
+            .map(|i| {
+                let length = i as f64 * self.time_step - t.max((i - 1) as f64 * self.time_step);
+                let count = ((length / max_step) - 1e-9).ceil().max(1.0) as usize;
+                (i, vec![length / count as f64; count])
+            })
+            .collect()
+    }
+}
+
+impl Model for HestonModel {
+    fn model_size(&self) -> usize {
+        self.model_size
+    }
+
+    fn maturity(&self) -> f64 {
+        self.maturity
+    }
+
+    fn fixings_dates_number(&self) -> usize {
+        self.fixings_dates_number
+    }
+
+    fn interest_rate(&self) -> f64 {
+        self.interest_rate
+    }
+
+    fn dividend_rate(&self) -> ArrayView1<'_, f64> {
+        self.dividend_rate.view()
+    }
+
+    fn spots(&self) -> ArrayView1<'_, f64> {
+        self.spots.view()
+    }
+
+    fn time_step(&self) -> f64 {
+        self.time_step
+    }
+
+    // deux gaussiennes par actif et par pas d'Euler
+    fn gaussians_number(&self, t: f64) -> usize {
+        let steps: usize = self.substeps(t).iter().map(|(_, dts)| dts.len()).sum();
+        2 * self.model_size * steps
+    }
+
+    // schéma d'Euler à troncature complète (v^+ = max(v, 0) dans les coefficients) sur log S :
+    // à variance figée sur un pas, E[S_{k+1} | F_k] = S_k e^{(r - q) dt} et les prix actualisés
+    // restent des martingales ; la variance en t > 0 n'est pas observée dans le passé, seule
+    // la simulation depuis t = 0 est donc possible
+    fn asset_from(
+        &self,
+        past: &Array2<f64>,
+        t: f64,
+        rng: &mut dyn RandomSource,
+    ) -> Result<Array2<f64>, PcpdError> {
+        let d = self.model_size;
+        let n = self.fixings_dates_number;
+        let r = self.interest_rate;
+        let last_index = self.last_fixing_index(t);
+
+        if t > 0.0 {
+            return Err(PcpdError::Model(format!(
+                "the heston model cannot simulate from t = {} > 0: the variance at t is unknown",
+                t
+            )));
+        }
+        check_past(past, t, last_index, d)?;
+
+        let mut path = Array2::<f64>::zeros((n + 1, d));
+        path.slice_mut(s![..=last_index, ..])
+            .assign(&past.slice(s![..=last_index, ..]));
+
+        // toutes les gaussiennes de la trajectoire en un seul appel : (W, B) par pas
+        let g = rng.normal_vec(self.gaussians_number(t));
+        let mut next = 0;
+
+        let mut log_spot = past.row(past.nrows() - 1).mapv(f64::ln);
+        let mut variance = self.v0.clone();
+
+        for (i, dts) in self.substeps(t) {
+            for dt in dts {
+                let z = self.l.dot(&Array1::from(g[next..next + d].to_vec()));
+                let b = &g[next + d..next + 2 * d];
+                next += 2 * d;
+
+                for j in 0..d {
+                    let v = variance[j].max(0.0);
+                    let rho = self.rho[j];
+                    let z_v = rho * z[j] + (1.0 - rho * rho).sqrt() * b[j];
+
+                    log_spot[j] +=
+                        (r - self.dividend_rate[j] - 0.5 * v) * dt + (v * dt).sqrt() * z[j];
+                    variance[j] += self.kappa[j] * (self.theta[j] - v) * dt
+                        + self.xi[j] * (v * dt).sqrt() * z_v;
+                }
+            }
+
+            path.row_mut(i).assign(&log_spot.mapv(f64::exp));
+        }
+
+        Ok(path)
+    }
+}
@@ -0,0 +1,202 @@
+use ndarray::{Array1, Array2, ArrayView1, s};
+use serde_json::Value;
+
+use crate::config::PricingConfig;
+use crate::error::PcpdError;
+use crate::math::random::normal_cdf;
+use crate::mc::rng::RandomSource;
+use crate::model::dynamics::{
+    Model, check_past, correlation_root, dividend_rate_from, required_parameter,
+};
+
+// modèle de Merton : Black-Scholes plus des sauts S_j -> J S_j, log J ~ N(m_j, delta_j^2),
+// aux instants d'un processus de Poisson d'intensité lambda_j (propre à chaque actif ou commun) ;
+// la dérive r - q_j - lambda_j k_j, k_j = E[J] - 1, compense les sauts
+pub struct MertonModel {
+    pub model_size: usize,            // nombre d'actifs du modèle
+    pub maturity: f64,                // maturité T
+    pub interest_rate: f64,           // taux d'intérêt
+    pub dividend_rate: Array1<f64>,   // taux de dividende continu par actif
+    pub correlation: f64,             // corrélation des browniens
+    pub volatility: Array1<f64>,      // volatilités de la partie continue
+    pub spots: Array1<f64>,           // valeurs initiales des sous-jacents
+    pub l: Array2<f64>,               // racine carrée de la matrice de corrélation
+    pub jump_intensity: Array1<f64>,  // lambda_j
+    pub jump_mean: Array1<f64>,       // m_j
+    pub jump_volatility: Array1<f64>, // delta_j
+    pub common_jumps: bool,           // tous les actifs sautent en même temps
+    pub fixings_dates_number: usize,  // nombre de dates de fixing N
+    pub time_step: f64,               // T / N
+}
+
+impl MertonModel {
+    pub fn from_json(json: &Value) -> Result<Self, PcpdError> {
+        let config = PricingConfig::from_json(json)?;
+        Self::from_config(&config)
+    }
+
+    pub fn from_config(config: &PricingConfig) -> Result<Self, PcpdError> {
+        let d = config.option_size;
+        let jump_intensity =
+            required_parameter("merton", "jump intensity", &config.jump_intensity)?;
+        let jump_mean = required_parameter("merton", "jump mean", &config.jump_mean)?;
+        let jump_volatility =
+            required_parameter("merton", "jump volatility", &config.jump_volatility)?;
+
+        if jump_intensity
+            .iter()
+            .chain(&jump_volatility)
+            .any(|&x| x < 0.0)
+        {
+            return Err(PcpdError::Model(
+                "\"jump intensity\" and \"jump volatility\" must be non-negative".to_string(),
+            ));
+        }
+        if config.common_jumps && jump_intensity.iter().any(|&x| x != jump_intensity[0]) {
+            return Err(PcpdError::Model(
+                "common jumps need the same \"jump intensity\" for every asset".to_string(),
+            ));
+        }
+
+        let n = config.fixing_dates_number;
+        Ok(MertonModel {
+            model_size: d,
+            maturity: config.maturity,
+            interest_rate: config.interest_rate,
+            dividend_rate: dividend_rate_from(&config.dividend_rate, d),
+            correlation: config.correlation,
+            volatility: Array1::from(config.volatility.clone()),
+            spots: Array1::from(config.spot.clone()),
+            l: correlation_root(config.correlation, d)?,
+            jump_intensity,
+            jump_mean,
+            jump_volatility,
+            common_jumps: config.common_jumps,
+            fixings_dates_number: n,
+            time_step: config.maturity / n as f64,
+        })
+    }
+}
+
+impl MertonModel {
+    // nombre de processus de Poisson simulés
+    fn poisson_processes(&self) -> usize {
+        if self.common_jumps {
+            1
+        } else {
+            self.model_size
+        }
+    }
+
+    // k_j = E[J_j] - 1
+    fn jump_compensator(&self) -> Array1<f64> {
+        (&self.jump_mean + &self.jump_volatility.mapv(|delta| 0.5 * delta * delta))
+            .mapv(f64::exp_m1)
+    }
+}
+
+impl Model for MertonModel {
+    fn model_size(&self) -> usize {
+        self.model_size
+    }
+
+    fn maturity(&self) -> f64 {
+        self.maturity
+    }
+
+    fn fixings_dates_number(&self) -> usize {
+        self.fixings_dates_number
+    }
+
+    fn interest_rate(&self) -> f64 {
+        self.interest_rate
+    }
+
+    fn dividend_rate(&self) -> ArrayView1<'_, f64> {
+        self.dividend_rate.view()
+    }
+
+    fn spots(&self) -> ArrayView1<'_, f64> {
+        self.spots.view()
+    }
+
+    fn time_step(&self) -> f64 {
+        self.time_step
+    }
+
+    // par pas : d gaussiennes pour les browniens, une par processus de Poisson (inversée en
+    // nombre de sauts) et d pour la somme des log-sauts
+    fn gaussians_number(&self, t: f64) -> usize {
+        let steps = self.fixings_dates_number - self.last_fixing_index(t);
+        steps * (2 * self.model_size + self.poisson_processes())
+    }
+
+    // simulation exacte d'une date de fixing à la suivante : sachant n sauts, la somme des
+    // log-sauts est N(n m, n delta^2)
+    fn asset_from(
+        &self,
+        past: &Array2<f64>,
+        t: f64,
+        rng: &mut dyn RandomSource,
+    ) -> Result<Array2<f64>, PcpdError> {
+        let d = self.model_size;
+        let n = self.fixings_dates_number;
+        let r = self.interest_rate;
+        let last_index = self.last_fixing_index(t);
+        let processes = self.poisson_processes();
+
+        check_past(past, t, last_index, d)?;
+
+        let mut path = Array2::<f64>::zeros((n + 1, d));
+        path.slice_mut(s![..=last_index, ..])
+            .assign(&past.slice(s![..=last_index, ..]));
+
+        // toutes les gaussiennes de la trajectoire en un seul appel
+        let g = rng.normal_vec(self.gaussians_number(t));
+        let mut next = 0;
+
+        let k = self.jump_compensator();
+        let mut current = past.row(past.nrows() - 1).to_owned();
+
+        for i in (last_index + 1)..=n {
+            let dt = i as f64 * self.time_step - t.max((i - 1) as f64 * self.time_step);
+
+            let z = self.l.dot(&Array1::from(g[next..next + d].to_vec()));
+            let poisson = &g[next + d..next + d + processes];
+            let sizes = &g[next + d + processes..next + 2 * d + processes];
+            next += 2 * d + processes;
+
+            for j in 0..d {
+                let sigma = self.volatility[j];
+                let lambda = self.jump_intensity[j];
+                let jumps = poisson_inverse(lambda * dt, normal_cdf(poisson[j % processes]));
+
+                let drift = (r - self.dividend_rate[j] - lambda * k[j] - 0.5 * sigma * sigma) * dt;
+                let diffusion = sigma * dt.sqrt() * z[j];
+                let jump = jumps as f64 * self.jump_mean[j]
+                    + (jumps as f64).sqrt() * self.jump_volatility[j] * sizes[j];
+
+                current[j] *= (drift + diffusion + jump).exp();
+            }
+
+            path.row_mut(i).assign(&current);
+        }
+
+        Ok(path)
+    }
+}
+
+// plus petit n tel que P(N <= n) >= u pour N de loi de Poisson de paramètre mean
+fn poisson_inverse(mean: f64, u: f64) -> usize {
+    let mut n = 0;
+    let mut probability = (-mean).exp();
+    let mut cdf = probability;
+
+    while cdf < u && probability > 0.0 {
+        n += 1;
+        probability *= mean / n as f64;
+        cdf += probability;
+    }
+
+    n
+}
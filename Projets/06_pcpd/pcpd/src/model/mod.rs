@@ -1 +1,6 @@
-mod black_scholes;
+pub mod analytic;
+pub mod black_scholes;
+pub mod dynamics;
+pub mod factory;
+pub mod heston;
+pub mod merton;
@@ -2,7 +2,9 @@ use ndarray::{Array1, Array2, Axis};
 use serde_json::Value;
 use std::ops::Sub;
 
-use crate::options::option::Option;
+use crate::config::PricingConfig;
+use crate::error::PcpdError;
+use crate::options::option::{Option, check_path};
 
 pub struct AsianOption {
     pub strike: f64,
@@ -13,7 +15,7 @@ pub struct AsianOption {
 impl AsianOption {
     pub fn new(strike: f64) -> Self {
         AsianOption {
-            strike: strike,
+            strike,
             model_size: 1,
             payoff_coeffcients: Array1::from(vec![1.0]),
         }
@@ -21,40 +23,49 @@ impl AsianOption {
 }
 
 impl AsianOption {
-    fn from_json(json: &Value) -> Self {
-        let strike = json["strike"].as_f64().unwrap();
-
-        let model_size = json["option size"].as_i64().unwrap() as usize;
-
-        let payoff_coeffcients: Array1<f64> = {
-            let mut coeff_: Vec<f64> = json["payoff coefficients"]
-                .as_array()
-                .unwrap()
-                .iter()
-                .map(|x| x.as_f64().unwrap())
-                .collect();
-
-            if coeff_.len() == 1 && model_size > 1 {
-                coeff_ = vec![coeff_[0]; model_size];
-            }
-
-            Array1::from(coeff_)
-        };
+    pub fn from_json(json: &Value) -> Result<Self, PcpdError> {
+        let config = PricingConfig::from_json(json)?;
+        Self::from_config(&config)
+    }
 
-        AsianOption {
-            strike: strike,
-            model_size: model_size,
-            payoff_coeffcients: payoff_coeffcients,
+    pub fn from_config(config: &PricingConfig) -> Result<Self, PcpdError> {
+        let strike = config.strike.ok_or_else(|| {
+            PcpdError::Config("\"strike\" is required for asian option".to_string())
+        })?;
+        if config.payoff_coefficients.is_empty() {
+            return Err(PcpdError::Config(
+                "\"payoff coefficients\" are required for asian option".to_string(),
+            ));
         }
+
+        Ok(AsianOption {
+            strike,
+            model_size: config.option_size,
+            payoff_coeffcients: Array1::from(config.payoff_coefficients.clone()),
+        })
     }
 }
 
 impl Option for AsianOption {
-    fn payoff(&self, path: &Array2<f64>) -> f64 {
-        path.mean_axis(Axis(0))
+    fn payoff(&self, path: &Array2<f64>) -> Result<f64, PcpdError> {
+        check_path(path, self.payoff_coeffcients.len())?;
+
+        Ok(path
+            .mean_axis(Axis(0))
             .unwrap()
             .dot(&self.payoff_coeffcients)
             .sub(self.strike)
-            .max(0.0)
+            .max(0.0))
+    }
+
+    fn gradient(&self, path: &Array2<f64>) -> Result<Array2<f64>, PcpdError> {
+        let mut gradient = Array2::zeros(path.dim());
+        if self.payoff(path)? > 0.0 {
+            let weights = &self.payoff_coeffcients / path.nrows() as f64;
+            for mut row in gradient.rows_mut() {
+                row.assign(&weights);
+            }
+        }
+        Ok(gradient)
     }
 }
@@ -0,0 +1,253 @@
+use ndarray::{Array1, Array2};
+use serde_json::Value;
+
+use crate::config::PricingConfig;
+use crate::error::PcpdError;
+use crate::model::analytic::VanillaKind;
+use crate::options::option::{Option, check_path};
+
+// constante de Broadie-Glasserman-Kou : -zeta(1/2) / sqrt(2 pi)
+const BGK_BETA: f64 = 0.5825971579390107;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarrierDirection {
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarrierKnock {
+    In,  // la vanille n'est payée que si la barrière a été franchie
+    Out, // la vanille n'est payée que si la barrière n'a pas été franchie
+}
+
+// correction de la surveillance discrète pour approcher une barrière continue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarrierCorrection {
+    None,   // surveillance aux seules dates de fixing
+    Shift,  // barrière décalée de exp(-+ beta sigma sqrt(dt)) (Broadie-Glasserman-Kou)
+    Bridge, // probabilité de franchissement du pont brownien entre deux dates de fixing
+}
+
+// option barrière sur le niveau du panier B_t = sum_j a_j S_t^j, observé aux dates de fixing
+// t_0 = 0, ..., t_N = T : payoff vanille (B_T - K)_+ ou (K - B_T)_+ selon le franchissement
+// de la barrière, rebate payé en T sinon
+pub struct BarrierOption {
+    pub kind: VanillaKind,
+    pub strike: f64,
+    pub barrier: f64,
+    pub direction: BarrierDirection,
+    pub knock: BarrierKnock,
+    pub rebate: f64,
+    pub payoff_coefficients: Array1<f64>,
+    pub correction: BarrierCorrection,
+    pub volatility: f64, // volatilité du niveau du panier, utilisée par les corrections
+    pub time_step: f64,  // écart entre deux dates de fixing
+}
+
+impl BarrierOption {
+    pub fn new(
+        kind: VanillaKind,
+        strike: f64,
+        barrier: f64,
+        direction: BarrierDirection,
+        knock: BarrierKnock,
+    ) -> Self {
+        BarrierOption {
+            kind,
+            strike,
+            barrier,
+            direction,
+            knock,
+            rebate: 0.0,
+            payoff_coefficients: Array1::from(vec![1.0]),
+            correction: BarrierCorrection::None,
+            volatility: 0.0,
+            time_step: 0.0,
+        }
+    }
+}
+
+impl BarrierOption {
+    pub fn from_json(json: &Value) -> Result<Self, PcpdError> {
+        let config = PricingConfig::from_json(json)?;
+        Self::from_config(&config)
+    }
+
+    pub fn from_config(config: &PricingConfig) -> Result<Self, PcpdError> {
+        let strike = config.strike.ok_or_else(|| {
+            PcpdError::Config("\"strike\" is required for barrier option".to_string())
+        })?;
+        let barrier = config.barrier.ok_or_else(|| {
+            PcpdError::Config("\"barrier\" is required for barrier option".to_string())
+        })?;
+        if barrier <= 0.0 {
+            return Err(PcpdError::Config(
+                "\"barrier\" must be positive".to_string(),
+            ));
+        }
+
+        let barrier_type = config.barrier_type.as_deref().ok_or_else(|| {
+            PcpdError::Config("\"barrier type\" is required for barrier option".to_string())
+        })?;
+        let (direction, knock) = match barrier_type {
+            "up-and-in" => (BarrierDirection::Up, BarrierKnock::In),
+            "up-and-out" => (BarrierDirection::Up, BarrierKnock::Out),
+            "down-and-in" => (BarrierDirection::Down, BarrierKnock::In),
+            "down-and-out" => (BarrierDirection::Down, BarrierKnock::Out),
+            other => {
+                return Err(PcpdError::Config(format!(
+                    "unknown barrier type \"{}\" (expected up-and-in, up-and-out, down-and-in or down-and-out)",
+                    other
+                )));
+            }
+        };
+
+        let kind = match config.barrier_payoff.as_deref().unwrap_or("call") {
+            "call" => VanillaKind::Call,
+            "put" => VanillaKind::Put,
+            other => {
+                return Err(PcpdError::Config(format!(
+                    "unknown barrier payoff \"{}\" (expected call or put)",
+                    other
+                )));
+            }
+        };
+
+        let correction = match config.barrier_correction.as_deref().unwrap_or("none") {
+            "none" => BarrierCorrection::None,
+            "shift" => BarrierCorrection::Shift,
+            "bridge" => BarrierCorrection::Bridge,
+            other => {
+                return Err(PcpdError::Config(format!(
+                    "unknown barrier correction \"{}\" (expected none, shift or bridge)",
+                    other
+                )));
+            }
+        };
+
+        // les corrections supposent un niveau log-normal de volatilité "volatility"
+        if correction != BarrierCorrection::None && config.model_type != "bs" {
+            return Err(PcpdError::Config(format!(
+                "\"barrier correction\" needs the bs model, not \"{}\"",
+                config.model_type
+            )));
+        }
+
+        // un seul actif : le niveau est S_t
+        let payoff_coefficients = match (config.payoff_coefficients.is_empty(), config.option_size)
+        {
+            (true, 1) => Array1::from(vec![1.0]),
+            (true, _) => {
+                return Err(PcpdError::Config(
+                    "\"payoff coefficients\" are required for barrier option on several assets"
+                        .to_string(),
+                ));
+            }
+            (false, _) => Array1::from(config.payoff_coefficients.clone()),
+        };
+
+        Ok(BarrierOption {
+            kind,
+            strike,
+            barrier,
+            direction,
+            knock,
+            rebate: config.rebate,
+            volatility: level_volatility(config, &payoff_coefficients),
+            payoff_coefficients,
+            correction,
+            time_step: config.maturity / config.fixing_dates_number as f64,
+        })
+    }
+
+    // barrière surveillée aux dates de fixing : décalée vers le spot pour la correction
+    // de Broadie-Glasserman-Kou, la barrière continue H correspondant à la barrière
+    // discrète H exp(+- beta sigma sqrt(dt))
+    fn monitored_barrier(&self) -> f64 {
+        match (self.correction, self.direction) {
+            (BarrierCorrection::Shift, BarrierDirection::Up) => {
+                self.barrier * (-BGK_BETA * self.volatility * self.time_step.sqrt()).exp()
+            }
+            (BarrierCorrection::Shift, BarrierDirection::Down) => {
+                self.barrier * (BGK_BETA * self.volatility * self.time_step.sqrt()).exp()
+            }
+            _ => self.barrier,
+        }
+    }
+
+    fn crossed(&self, level: f64, barrier: f64) -> bool {
+        match self.direction {
+            BarrierDirection::Up => level >= barrier,
+            BarrierDirection::Down => level <= barrier,
+        }
+    }
+
+    // probabilité que le niveau ne franchisse pas la barrière sur la trajectoire
+    fn survival_probability(&self, levels: &Array1<f64>) -> f64 {
+        let barrier = self.monitored_barrier();
+        if levels.iter().any(|&level| self.crossed(level, barrier)) {
+            return 0.0;
+        }
+        if self.correction != BarrierCorrection::Bridge || self.volatility <= 0.0 {
+            return 1.0;
+        }
+
+        // log B approché par un brownien de volatilité sigma entre deux dates de fixing :
+        // sachant les extrémités, il franchit log H avec probabilité
+        // exp(-2 log(H / B_i) log(H / B_{i+1}) / (sigma^2 dt))
+        let variance = self.volatility * self.volatility * self.time_step;
+        levels
+            .windows(2)
+            .into_iter()
+            .map(|pair| {
+                let distance = (barrier / pair[0]).ln() * (barrier / pair[1]).ln();
+                1.0 - (-2.0 * distance / variance).exp()
+            })
+            .product()
+    }
+}
+
+impl Option for BarrierOption {
+    fn payoff(&self, path: &Array2<f64>) -> Result<f64, PcpdError> {
+        check_path(path, self.payoff_coefficients.len())?;
+
+        let levels = path.dot(&self.payoff_coefficients);
+        let level_t = levels[levels.len() - 1];
+        let vanilla = match self.kind {
+            VanillaKind::Call => (level_t - self.strike).max(0.0),
+            VanillaKind::Put => (self.strike - level_t).max(0.0),
+        };
+
+        let survival = self.survival_probability(&levels);
+        let active = match self.knock {
+            BarrierKnock::In => 1.0 - survival,
+            BarrierKnock::Out => survival,
+        };
+
+        Ok(active * vanilla + (1.0 - active) * self.rebate)
+    }
+}
+
+// volatilité du niveau du panier en t = 0 : sqrt(sum_ij w_i w_j rho_ij sigma_i sigma_j), où
+// w_j = a_j S_0^j / B_0 ; exacte pour un seul actif, approchée pour un panier
+fn level_volatility(config: &PricingConfig, coefficients: &Array1<f64>) -> f64 {
+    let d = coefficients.len().min(config.spot.len());
+    let level: f64 = (0..d).map(|j| coefficients[j] * config.spot[j]).sum();
+    if level == 0.0 {
+        return 0.0;
+    }
+
+    let weights: Vec<f64> = (0..d)
+        .map(|j| coefficients[j] * config.spot[j] * config.volatility[j] / level)
+        .collect();
+    let mut variance = 0.0;
+    for i in 0..d {
+        for j in 0..d {
+            let rho = if i == j { 1.0 } else { config.correlation };
+            variance += rho * weights[i] * weights[j];
+        }
+    }
+
+    variance.max(0.0).sqrt()
+}
@@ -5,7 +5,9 @@ use serde_json::Value;
 
 // use crate::{ options::{asian::AsianOption, option::Option}};
 
-use crate::options::option::Option;
+use crate::config::PricingConfig;
+use crate::error::PcpdError;
+use crate::options::option::{Option, check_path};
 
 pub struct BasketOption {
     pub strike: f64,
@@ -16,7 +18,7 @@ pub struct BasketOption {
 impl BasketOption {
     pub fn new(strike: f64) -> Self {
         BasketOption {
-            strike: strike,
+            strike,
             model_size: 1,
             payoff_coeffcients: Array1::from(vec![1.0]),
         }
@@ -24,41 +26,47 @@ impl BasketOption {
 }
 
 impl BasketOption {
-    fn from_json(json: &Value) -> Self {
-        let strike = json["strike"].as_f64().unwrap();
-
-        let model_size = json["option size"].as_i64().unwrap() as usize;
-
-        let payoff_coeff: Array1<f64> = {
-            let mut coeff_: Vec<f64> = json["payoff coefficients"]
-                .as_array()
-                .unwrap()
-                .iter()
-                .map(|x| x.as_f64().unwrap())
-                .collect();
-
-            if coeff_.len() == 1 && model_size > 1 {
-                coeff_ = vec![coeff_[0]; model_size];
-            }
-
-            Array1::from(coeff_)
-        };
+    pub fn from_json(json: &Value) -> Result<Self, PcpdError> {
+        let config = PricingConfig::from_json(json)?;
+        Self::from_config(&config)
+    }
 
-        BasketOption {
-            strike: strike,
-            model_size: model_size,
-            payoff_coeffcients: payoff_coeff,
+    pub fn from_config(config: &PricingConfig) -> Result<Self, PcpdError> {
+        let strike = config.strike.ok_or_else(|| {
+            PcpdError::Config("\"strike\" is required for basket option".to_string())
+        })?;
+        if config.payoff_coefficients.is_empty() {
+            return Err(PcpdError::Config(
+                "\"payoff coefficients\" are required for basket option".to_string(),
+            ));
         }
+
+        Ok(BasketOption {
+            strike,
+            model_size: config.option_size,
+            payoff_coeffcients: Array1::from(config.payoff_coefficients.clone()),
+        })
     }
 }
 
 impl Option for BasketOption {
-    fn payoff(&self, path: &Array2<f64>) -> f64 {
-        assert!(path.nrows() > 0, "Path is empty!");
+    fn payoff(&self, path: &Array2<f64>) -> Result<f64, PcpdError> {
+        check_path(path, self.payoff_coeffcients.len())?;
 
-        path.row(path.nrows() - 1)
+        Ok(path
+            .row(path.nrows() - 1)
             .dot(&self.payoff_coeffcients)
             .sub(self.strike)
-            .max(0.0)
+            .max(0.0))
+    }
+
+    fn gradient(&self, path: &Array2<f64>) -> Result<Array2<f64>, PcpdError> {
+        let mut gradient = Array2::zeros(path.dim());
+        if self.payoff(path)? > 0.0 {
+            gradient
+                .row_mut(path.nrows() - 1)
+                .assign(&self.payoff_coeffcients);
+        }
+        Ok(gradient)
     }
 }
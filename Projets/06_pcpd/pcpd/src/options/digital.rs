@@ -0,0 +1,32 @@
+use crate::config::PricingConfig;
+use crate::error::PcpdError;
+use crate::options::option::{Option, check_path};
+
+// call digital : paie 1 si S_T > K, payoff discontinu (pas de gradient)
+pub struct DigitalOption {
+    pub strike: f64,
+}
+
+impl DigitalOption {
+    pub fn new(strike: f64) -> Self {
+        DigitalOption { strike }
+    }
+
+    pub fn from_config(config: &PricingConfig) -> Result<Self, PcpdError> {
+        let strike = config.strike.ok_or_else(|| {
+            PcpdError::Config("\"strike\" is required for digital option".to_string())
+        })?;
+
+        Ok(DigitalOption::new(strike))
+    }
+}
+
+impl Option for DigitalOption {
+    fn payoff(&self, path: &ndarray::Array2<f64>) -> Result<f64, PcpdError> {
+        check_path(path, 1)?;
+
+        let s_t = path[[path.nrows() - 1, 0]];
+
+        Ok(if s_t > self.strike { 1.0 } else { 0.0 })
+    }
+}
@@ -0,0 +1,33 @@
+use serde_json::Value;
+
+use crate::config::PricingConfig;
+use crate::error::PcpdError;
+use crate::options::asian::AsianOption;
+use crate::options::barrier::BarrierOption;
+use crate::options::basket::BasketOption;
+use crate::options::call::CallOption;
+use crate::options::digital::DigitalOption;
+use crate::options::option::Option;
+use crate::options::perf::PerformanceOption;
+use crate::options::put::PutOption;
+
+// construit l'option décrite par le champ "option type"
+pub fn option_from_config(config: &PricingConfig) -> Result<Box<dyn Option>, PcpdError> {
+    let option: Box<dyn Option> = match config.option_type.as_str() {
+        "basket" => Box::new(BasketOption::from_config(config)?),
+        "asian" => Box::new(AsianOption::from_config(config)?),
+        "performance" => Box::new(PerformanceOption::from_config(config)?),
+        "call" => Box::new(CallOption::from_config(config)?),
+        "put" => Box::new(PutOption::from_config(config)?),
+        "digital" => Box::new(DigitalOption::from_config(config)?),
+        "barrier" => Box::new(BarrierOption::from_config(config)?),
+        other => return Err(PcpdError::UnknownOptionType(other.to_string())),
+    };
+
+    Ok(option)
+}
+
+pub fn option_from_json(json: &Value) -> Result<Box<dyn Option>, PcpdError> {
+    let config = PricingConfig::from_json(json)?;
+    option_from_config(&config)
+}
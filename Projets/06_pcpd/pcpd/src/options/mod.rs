@@ -1,6 +1,9 @@
 pub mod asian;
+pub mod barrier;
 pub mod basket;
 pub mod call;
+pub mod digital;
+pub mod factory;
 pub mod option;
 pub mod perf;
 pub mod put;
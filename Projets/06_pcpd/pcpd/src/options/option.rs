@@ -1,5 +1,31 @@
 use ndarray::Array2;
 
-pub trait Option {
-    fn payoff(&self, path: &Array2<f64>) -> f64;
+use crate::error::PcpdError;
+
+// Send + Sync : le pricer évalue les payoffs depuis plusieurs threads
+pub trait Option: Send + Sync {
+    fn payoff(&self, path: &Array2<f64>) -> Result<f64, PcpdError>;
+
+    // dérivée du payoff par rapport à chaque S_{t_i}^j, pour les estimateurs trajectoriels ;
+    // seuls les payoffs lipschitziens (dérivables presque partout) la fournissent
+    fn gradient(&self, _path: &Array2<f64>) -> Result<Array2<f64>, PcpdError> {
+        Err(PcpdError::Payoff(
+            "payoff is not Lipschitz: use likelihood-ratio greeks".to_string(),
+        ))
+    }
+}
+
+// vérifie qu'une trajectoire est non vide et a le bon nombre d'actifs
+pub fn check_path(path: &Array2<f64>, model_size: usize) -> Result<(), PcpdError> {
+    if path.nrows() == 0 {
+        return Err(PcpdError::Payoff("path is empty".to_string()));
+    }
+    if path.ncols() != model_size {
+        return Err(PcpdError::Payoff(format!(
+            "path has {} columns but the option expects {}",
+            path.ncols(),
+            model_size
+        )));
+    }
+    Ok(())
 }
@@ -0,0 +1,76 @@
+use ndarray::{Array1, Array2};
+use serde_json::Value;
+
+use crate::config::PricingConfig;
+use crate::error::PcpdError;
+use crate::options::option::{Option, check_path};
+
+pub struct PerformanceOption {
+    pub model_size: usize,
+    pub payoff_coeffcients: Array1<f64>,
+}
+
+impl PerformanceOption {
+    pub fn new(payoff_coeffcients: Array1<f64>) -> Self {
+        PerformanceOption {
+            model_size: payoff_coeffcients.len(),
+            payoff_coeffcients,
+        }
+    }
+}
+
+impl PerformanceOption {
+    pub fn from_json(json: &Value) -> Result<Self, PcpdError> {
+        let config = PricingConfig::from_json(json)?;
+        Self::from_config(&config)
+    }
+
+    pub fn from_config(config: &PricingConfig) -> Result<Self, PcpdError> {
+        if config.payoff_coefficients.is_empty() {
+            return Err(PcpdError::Config(
+                "\"payoff coefficients\" are required for performance option".to_string(),
+            ));
+        }
+
+        Ok(PerformanceOption::new(Array1::from(
+            config.payoff_coefficients.clone(),
+        )))
+    }
+}
+
+impl Option for PerformanceOption {
+    // 1 + somme des performances positives du panier entre deux dates de fixing consécutives
+    fn payoff(&self, path: &Array2<f64>) -> Result<f64, PcpdError> {
+        check_path(path, self.payoff_coeffcients.len())?;
+
+        let basket = path.dot(&self.payoff_coeffcients);
+
+        let performance: f64 = basket
+            .windows(2)
+            .into_iter()
+            .map(|w| (w[1] / w[0] - 1.0).max(0.0))
+            .sum();
+
+        Ok(1.0 + performance)
+    }
+
+    // d(B_i / B_{i-1}) / dS_i^j = lambda_j / B_{i-1}, d(B_i / B_{i-1}) / dS_{i-1}^j = -lambda_j B_i / B_{i-1}^2
+    fn gradient(&self, path: &Array2<f64>) -> Result<Array2<f64>, PcpdError> {
+        check_path(path, self.payoff_coeffcients.len())?;
+
+        let basket = path.dot(&self.payoff_coeffcients);
+        let mut gradient = Array2::zeros(path.dim());
+
+        for i in 1..basket.len() {
+            if basket[i] > basket[i - 1] {
+                let previous = basket[i - 1];
+                for (j, lambda) in self.payoff_coeffcients.iter().enumerate() {
+                    gradient[[i, j]] += lambda / previous;
+                    gradient[[i - 1, j]] -= lambda * basket[i] / (previous * previous);
+                }
+            }
+        }
+
+        Ok(gradient)
+    }
+}
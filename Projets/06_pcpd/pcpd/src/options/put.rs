@@ -1,4 +1,6 @@
-use crate::options::option::Option;
+use crate::config::PricingConfig;
+use crate::error::PcpdError;
+use crate::options::option::{Option, check_path};
 
 pub struct PutOption {
     strike: f64,
@@ -6,14 +8,32 @@ pub struct PutOption {
 
 impl PutOption {
     pub fn new(strike: f64) -> Self {
-        PutOption { strike: strike }
+        PutOption { strike }
+    }
+
+    pub fn from_config(config: &PricingConfig) -> Result<Self, PcpdError> {
+        let strike = config.strike.ok_or_else(|| {
+            PcpdError::Config("\"strike\" is required for put option".to_string())
+        })?;
+
+        Ok(PutOption::new(strike))
     }
 }
 
 impl Option for PutOption {
-    fn payoff(&self, path: &ndarray::Array2<f64>) -> f64 {
+    fn payoff(&self, path: &ndarray::Array2<f64>) -> Result<f64, PcpdError> {
+        check_path(path, 1)?;
+
         let s_t = path[[path.nrows() - 1, 0]];
 
-        (self.strike - s_t).max(0.0)
+        Ok((self.strike - s_t).max(0.0))
+    }
+
+    fn gradient(&self, path: &ndarray::Array2<f64>) -> Result<ndarray::Array2<f64>, PcpdError> {
+        let mut gradient = ndarray::Array2::zeros(path.dim());
+        if self.payoff(path)? > 0.0 {
+            gradient[[path.nrows() - 1, 0]] = -1.0;
+        }
+        Ok(gradient)
     }
 }
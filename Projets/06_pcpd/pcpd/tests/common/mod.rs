@@ -0,0 +1,30 @@
+// fonctions partagées par les tests : chaque binaire n'en utilise qu'une partie
+#![allow(dead_code)]
+
+use pcpd::config::PricingConfig;
+use pcpd::mc::pricer::MonteCarlo;
+use pcpd::options::factory::option_from_config;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+// lecture d'un fichier JSON relatif à la racine du crate (ou d'un chemin absolu)
+pub fn read_json(path: impl AsRef<Path>) -> Value {
+    let full_path = Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+    let data = fs::read_to_string(full_path).expect("Impossible de lire le fichier");
+    serde_json::from_str(&data).expect("JSON invalide")
+}
+
+// configuration relative à la racine du crate
+pub fn config(path: &str) -> PricingConfig {
+    PricingConfig::from_file(&format!("{}/{}", env!("CARGO_MANIFEST_DIR"), path)).unwrap()
+}
+
+// pricer décrit par un fichier de configuration, avec sample_number tirages
+pub fn pricer(path: &str, sample_number: usize) -> MonteCarlo {
+    let mut config = config(path);
+    config.sample_number = sample_number;
+
+    let option = option_from_config(&config).unwrap();
+    MonteCarlo::from_config(&config, option).unwrap()
+}
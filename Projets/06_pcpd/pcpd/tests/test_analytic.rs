@@ -0,0 +1,157 @@
+use pcpd::math::random::normal_cdf;
+use pcpd::mc::pricer::MonteCarlo;
+use pcpd::mc::rng::Xoshiro256PlusPlus;
+use pcpd::model::analytic::{AnalyticVanilla, Greeks, VanillaKind};
+use pcpd::options::factory::option_from_config;
+use pcpd::options::put::PutOption;
+use rand::SeedableRng;
+
+mod common;
+use common::config;
+
+fn vanilla(kind: VanillaKind) -> AnalyticVanilla {
+    AnalyticVanilla::new(kind, 100.0, 100.0, 1.0, 0.05, 0.0, 0.2)
+}
+
+#[test]
+fn test_normal_cdf() {
+    assert_eq!(normal_cdf(0.0), 0.5);
+    assert!((normal_cdf(1.96) - 0.9750021048517795).abs() < 1e-14);
+    assert!((normal_cdf(-3.0) - 0.0013498980316301).abs() < 1e-15);
+    assert!((normal_cdf(-10.0) / 7.619853024160527e-24 - 1.0).abs() < 1e-6);
+    assert!((normal_cdf(0.7) + normal_cdf(-0.7) - 1.0).abs() < 1e-15);
+}
+
+#[test]
+fn test_reference_prices() {
+    // S = K = 100, T = 1, r = 5%, sigma = 20%
+    assert!((vanilla(VanillaKind::Call).price() - 10.450583572185565).abs() < 1e-10);
+    assert!((vanilla(VanillaKind::Put).price() - 5.573526022256971).abs() < 1e-10);
+}
+
+#[test]
+fn test_put_call_parity_with_dividends() {
+    let mut call = vanilla(VanillaKind::Call);
+    call.dividend_yield = 0.03;
+    let mut put = vanilla(VanillaKind::Put);
+    put.dividend_yield = 0.03;
+
+    let forward = 100.0 * (-0.03f64).exp() - 100.0 * (-0.05f64).exp();
+    assert!((call.price() - put.price() - forward).abs() < 1e-10);
+    assert!((call.delta() - put.delta() - (-0.03f64).exp()).abs() < 1e-12);
+}
+
+#[test]
+fn test_greeks_match_finite_differences() {
+    for kind in [VanillaKind::Call, VanillaKind::Put] {
+        let mut base = vanilla(kind);
+        base.dividend_yield = 0.02;
+        let greeks: Greeks = base.greeks();
+        let h = 1e-4;
+
+        let bumped = |f: &dyn Fn(&mut AnalyticVanilla, f64)| {
+            let (mut up, mut down) = (base, base);
+            f(&mut up, h);
+            f(&mut down, -h);
+            (up.price(), down.price())
+        };
+
+        let (up, down) = bumped(&|v, e| v.spot += e);
+        assert!((greeks.delta - (up - down) / (2.0 * h)).abs() < 1e-6);
+        assert!((greeks.gamma - (up - 2.0 * base.price() + down) / (h * h)).abs() < 1e-4);
+
+        let (up, down) = bumped(&|v, e| v.volatility += e);
+        assert!((greeks.vega - (up - down) / (2.0 * h)).abs() < 1e-5);
+
+        let (up, down) = bumped(&|v, e| v.interest_rate += e);
+        assert!((greeks.rho - (up - down) / (2.0 * h)).abs() < 1e-5);
+
+        // theta : dérivée par rapport au temps qui passe, i.e. -dV/dT
+        let (up, down) = bumped(&|v, e| v.maturity += e);
+        assert!((greeks.theta + (up - down) / (2.0 * h)).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn test_expired_option_is_intrinsic() {
+    let mut call = vanilla(VanillaKind::Call);
+    call.maturity = 0.0;
+    call.spot = 110.0;
+
+    let greeks = call.greeks();
+    assert_eq!(greeks.price, 10.0);
+    assert_eq!(greeks.delta, 1.0);
+    assert_eq!(greeks.gamma, 0.0);
+}
+
+#[test]
+fn test_monte_carlo_call_matches_closed_form() {
+    let config = config("data/call/call.json");
+    let analytic = AnalyticVanilla::from_config(&config).unwrap();
+    let option = option_from_config(&config).unwrap();
+    let mut pricer = MonteCarlo::from_config(&config, option).unwrap();
+    // petit pas pour que le biais des différences finies soit négligeable
+    pricer.fd_step = 0.01;
+
+    let result = pricer
+        .price_and_delta(&mut Xoshiro256PlusPlus::seed_from_u64(31))
+        .unwrap();
+    let greeks = analytic.greeks();
+
+    assert!(
+        (result.price - greeks.price).abs() < 5.0 * result.price_std_dev,
+        "MC price {} +- {} vs closed form {}",
+        result.price,
+        result.price_std_dev,
+        greeks.price
+    );
+    assert!(
+        (result.delta[0] - greeks.delta).abs() < 5.0 * result.delta_std_dev[0],
+        "MC delta {} +- {} vs closed form {}",
+        result.delta[0],
+        result.delta_std_dev[0],
+        greeks.delta
+    );
+}
+
+#[test]
+fn test_monte_carlo_put_matches_closed_form() {
+    let config = config("data/call/call.json");
+    let mut analytic = AnalyticVanilla::from_config(&config).unwrap();
+    analytic.kind = VanillaKind::Put;
+    let mut pricer = MonteCarlo::from_config(&config, Box::new(PutOption::new(100.0))).unwrap();
+    pricer.fd_step = 0.01;
+
+    let result = pricer
+        .price_and_delta(&mut Xoshiro256PlusPlus::seed_from_u64(32))
+        .unwrap();
+    let greeks = analytic.greeks();
+
+    assert!((result.price - greeks.price).abs() < 5.0 * result.price_std_dev);
+    assert!((result.delta[0] - greeks.delta).abs() < 5.0 * result.delta_std_dev[0]);
+}
+
+#[test]
+fn test_no_closed_form_for_asian() {
+    let config = config("data/asian/asian.json");
+    assert!(AnalyticVanilla::from_config(&config).is_err());
+}
+
+#[test]
+fn test_monte_carlo_call_with_dividends_matches_closed_form() {
+    let mut config = config("data/call/call.json");
+    config.dividend_rate = vec![0.03];
+    let analytic = AnalyticVanilla::from_config(&config).unwrap();
+    let option = option_from_config(&config).unwrap();
+    let mut pricer = MonteCarlo::from_config(&config, option).unwrap();
+    pricer.fd_step = 0.01;
+
+    let result = pricer
+        .price_and_delta(&mut Xoshiro256PlusPlus::seed_from_u64(33))
+        .unwrap();
+    let greeks = analytic.greeks();
+
+    assert_eq!(analytic.dividend_yield, 0.03);
+    assert!((result.price - greeks.price).abs() < 5.0 * result.price_std_dev);
+    assert!((result.delta[0] - greeks.delta).abs() < 5.0 * result.delta_std_dev[0]);
+}
@@ -0,0 +1,307 @@
+use ndarray::array;
+use pcpd::config::PricingConfig;
+use pcpd::error::PcpdError;
+use pcpd::mc::pricer::MonteCarlo;
+use pcpd::mc::rng::Xoshiro256PlusPlus;
+use pcpd::model::analytic::{AnalyticBarrier, AnalyticVanilla, VanillaKind};
+use pcpd::options::barrier::{BarrierDirection, BarrierKnock, BarrierOption};
+use pcpd::options::factory::option_from_config;
+use pcpd::options::option::Option;
+use rand::SeedableRng;
+use serde_json::{Value, json};
+
+mod common;
+use common::read_json;
+
+fn barrier_json(barrier_type: &str, payoff: &str, barrier: f64, correction: &str) -> Value {
+    let mut json = read_json("data/barrier/barrier.json");
+    json["barrier type"] = json!(barrier_type);
+    json["barrier payoff"] = json!(payoff);
+    json["barrier"] = json!(barrier);
+    json["barrier correction"] = json!(correction);
+    json
+}
+
+fn mc_price(json: &Value, seed: u64) -> (f64, f64) {
+    let config = PricingConfig::from_json(json).unwrap();
+    let option = option_from_config(&config).unwrap();
+    let mut pricer = MonteCarlo::from_config(&config, option).unwrap();
+    pricer.sample_number = 100000;
+
+    let result = pricer
+        .price(&mut Xoshiro256PlusPlus::seed_from_u64(seed))
+        .unwrap();
+    (result.price, result.price_std_dev)
+}
+
+fn analytic_price(json: &Value) -> f64 {
+    let config = PricingConfig::from_json(json).unwrap();
+    AnalyticBarrier::from_config(&config).unwrap().price()
+}
+
+#[test]
+fn test_payoff_on_the_fixing_grid() {
+    let mut option = BarrierOption::new(
+        VanillaKind::Call,
+        100.0,
+        120.0,
+        BarrierDirection::Up,
+        BarrierKnock::Out,
+    );
+    option.rebate = 2.0;
+
+    assert_eq!(
+        option.payoff(&array![[100.0], [110.0], [115.0]]).unwrap(),
+        15.0
+    );
+    assert_eq!(
+        option.payoff(&array![[100.0], [125.0], [115.0]]).unwrap(),
+        2.0
+    );
+
+    option.knock = BarrierKnock::In;
+    assert_eq!(
+        option.payoff(&array![[100.0], [110.0], [115.0]]).unwrap(),
+        2.0
+    );
+    assert_eq!(
+        option.payoff(&array![[100.0], [125.0], [115.0]]).unwrap(),
+        15.0
+    );
+
+    // le niveau d'un panier est sum_j a_j S^j
+    let mut basket = BarrierOption::new(
+        VanillaKind::Put,
+        100.0,
+        80.0,
+        BarrierDirection::Down,
+        BarrierKnock::Out,
+    );
+    basket.payoff_coefficients = array![0.5, 0.5];
+    let path = array![[100.0, 100.0], [60.0, 95.0], [90.0, 96.0]];
+    assert_eq!(basket.payoff(&path).unwrap(), 0.0);
+    let path = array![[100.0, 100.0], [70.0, 100.0], [90.0, 96.0]];
+    assert_eq!(basket.payoff(&path).unwrap(), 7.0);
+}
+
+#[test]
+fn test_in_out_parity_is_pathwise() {
+    let paths = [
+        array![[100.0], [110.0], [119.0], [105.0]],
+        array![[100.0], [121.0], [119.0], [130.0]],
+        array![[100.0], [90.0], [80.0], [85.0]],
+    ];
+
+    for correction in ["none", "shift", "bridge"] {
+        let mut json = barrier_json("up-and-out", "call", 120.0, correction);
+        json["rebate"] = json!(3.0);
+        json["fixing dates number"] = json!(3);
+        let out = option_from_config(&PricingConfig::from_json(&json).unwrap()).unwrap();
+        json["barrier type"] = json!("up-and-in");
+        let knock_in = option_from_config(&PricingConfig::from_json(&json).unwrap()).unwrap();
+
+        for path in &paths {
+            let vanilla = (path[[3, 0]] - 100.0f64).max(0.0);
+            let sum = out.payoff(path).unwrap() + knock_in.payoff(path).unwrap();
+            assert!((sum - vanilla - 3.0).abs() < 1e-12, "{}", correction);
+        }
+    }
+}
+
+#[test]
+fn test_bridge_lowers_the_knock_out_payoff() {
+    let mut json = barrier_json("up-and-out", "call", 120.0, "none");
+    json["fixing dates number"] = json!(2);
+    let path = array![[100.0], [118.0], [115.0]];
+
+    let discrete = option_from_config(&PricingConfig::from_json(&json).unwrap()).unwrap();
+    json["barrier correction"] = json!("bridge");
+    let bridge = option_from_config(&PricingConfig::from_json(&json).unwrap()).unwrap();
+
+    // le pont entre 118 et 115 a pu toucher 120
+    let discrete_payoff = discrete.payoff(&path).unwrap();
+    let bridge_payoff = bridge.payoff(&path).unwrap();
+    assert_eq!(discrete_payoff, 15.0);
+    assert!(bridge_payoff > 0.0 && bridge_payoff < discrete_payoff);
+}
+
+#[test]
+fn test_analytic_reference_prices() {
+    // Haug, The Complete Guide to Option Pricing Formulas : S = 100, T = 0.5, r = 8%,
+    // q = 4%, sigma = 25%, rebate 3 payé en T pour les options "in"
+    let cases = [
+        ("down-and-in", "call", 95.0, 90.0, 7.7627),
+        ("down-and-in", "call", 95.0, 100.0, 4.0109),
+        ("down-and-in", "call", 95.0, 110.0, 2.0576),
+        ("up-and-in", "call", 105.0, 90.0, 14.1112),
+        ("up-and-in", "call", 105.0, 100.0, 8.4482),
+        ("up-and-in", "call", 105.0, 110.0, 4.5910),
+        ("down-and-in", "put", 95.0, 90.0, 2.9586),
+        ("down-and-in", "put", 95.0, 100.0, 6.5677),
+        ("down-and-in", "put", 95.0, 110.0, 11.9752),
+        ("up-and-in", "put", 105.0, 90.0, 1.4653),
+        ("up-and-in", "put", 105.0, 100.0, 3.3721),
+        ("up-and-in", "put", 105.0, 110.0, 7.0846),
+    ];
+
+    for (barrier_type, payoff, barrier, strike, expected) in cases {
+        let mut json = barrier_json(barrier_type, payoff, barrier, "none");
+        json["strike"] = json!(strike);
+        json["maturity"] = json!(0.5);
+        json["interest rate"] = json!(0.08);
+        json["dividend rate"] = json!([0.04]);
+        json["volatility"] = json!([0.25]);
+        json["rebate"] = json!(3.0);
+
+        let price = analytic_price(&json);
+        assert!(
+            (price - expected).abs() < 1e-3,
+            "{} {} H = {} K = {}: {} vs {}",
+            barrier_type,
+            payoff,
+            barrier,
+            strike,
+            price,
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_analytic_in_out_parity() {
+    for (direction, barrier) in [("up", 120.0), ("down", 85.0)] {
+        for (payoff, kind) in [("call", VanillaKind::Call), ("put", VanillaKind::Put)] {
+            let mut json = barrier_json(&format!("{}-and-in", direction), payoff, barrier, "none");
+            json["rebate"] = json!(2.0);
+            let knock_in = analytic_price(&json);
+            json["barrier type"] = json!(format!("{}-and-out", direction));
+            let out = analytic_price(&json);
+
+            let r = json["interest rate"].as_f64().unwrap();
+            let vanilla = AnalyticVanilla::new(kind, 100.0, 100.0, 1.0, r, 0.0, 0.2).price();
+            assert!((knock_in + out - vanilla - 2.0 * (-r).exp()).abs() < 1e-10);
+        }
+    }
+}
+
+#[test]
+fn test_bridge_matches_continuous_price() {
+    let cases = [
+        ("up-and-out", "call", 120.0, 0.0),
+        ("down-and-in", "put", 85.0, 1.5),
+        ("down-and-out", "call", 90.0, 2.0),
+    ];
+
+    for (seed, (barrier_type, payoff, barrier, rebate)) in cases.into_iter().enumerate() {
+        let mut json = barrier_json(barrier_type, payoff, barrier, "bridge");
+        json["fixing dates number"] = json!(12);
+        json["rebate"] = json!(rebate);
+
+        let (price, std_dev) = mc_price(&json, seed as u64);
+        let expected = analytic_price(&json);
+        assert!(
+            (price - expected).abs() < 5.0 * std_dev,
+            "{}: {} +- {} vs {}",
+            barrier_type,
+            price,
+            std_dev,
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_shift_corrects_discrete_monitoring() {
+    let mut json = barrier_json("up-and-out", "call", 120.0, "none");
+    json["fixing dates number"] = json!(52);
+    let expected = analytic_price(&json);
+
+    // surveillance discrète : la barrière est moins souvent touchée, le prix est trop élevé
+    let (discrete, std_dev) = mc_price(&json, 10);
+    assert!(discrete > expected + 5.0 * std_dev);
+
+    // la correction n'est exacte qu'asymptotiquement : on tolère un biais résiduel
+    json["barrier correction"] = json!("shift");
+    let (shifted, std_dev) = mc_price(&json, 11);
+    assert!(
+        (shifted - expected).abs() < 5.0 * std_dev + 0.02,
+        "{} +- {} vs {}",
+        shifted,
+        std_dev,
+        expected
+    );
+}
+
+#[test]
+fn test_basket_barrier_in_out_parity() {
+    let mut json = read_json("data/basket/basket_2d/basket_2d.json");
+    json["sample number"] = json!(5000);
+    let vanilla_config = PricingConfig::from_json(&json).unwrap();
+    let vanilla_option = option_from_config(&vanilla_config).unwrap();
+    let vanilla = MonteCarlo::from_config(&vanilla_config, vanilla_option)
+        .unwrap()
+        .price(&mut Xoshiro256PlusPlus::seed_from_u64(12))
+        .unwrap();
+
+    let mut prices = Vec::new();
+    for barrier_type in ["down-and-in", "down-and-out"] {
+        json["option type"] = json!("barrier");
+        json["barrier type"] = json!(barrier_type);
+        json["barrier"] = json!(80.0);
+        json["barrier correction"] = json!("bridge");
+        let config = PricingConfig::from_json(&json).unwrap();
+        let option = option_from_config(&config).unwrap();
+        let pricer = MonteCarlo::from_config(&config, option).unwrap();
+        prices.push(
+            pricer
+                .price(&mut Xoshiro256PlusPlus::seed_from_u64(12))
+                .unwrap()
+                .price,
+        );
+    }
+
+    assert!(prices.iter().all(|&price| price > 0.0));
+    assert!((prices[0] + prices[1] - vanilla.price).abs() < 1e-8);
+}
+
+#[test]
+fn test_barrier_config_errors() {
+    let mut json = barrier_json("up-and-out", "call", 120.0, "none");
+    json.as_object_mut().unwrap().remove("barrier");
+    let error = BarrierOption::from_json(&json).err().unwrap().to_string();
+    assert!(error.contains("barrier"), "{}", error);
+
+    let json = barrier_json("sideways-and-out", "call", 120.0, "none");
+    assert!(matches!(
+        BarrierOption::from_json(&json),
+        Err(PcpdError::Config(_))
+    ));
+
+    let mut json = barrier_json("up-and-out", "call", 120.0, "bridge");
+    json["model type"] = json!("heston");
+    json["kappa"] = json!([2.0]);
+    json["theta"] = json!([0.04]);
+    json["xi"] = json!([0.3]);
+    assert!(matches!(
+        BarrierOption::from_json(&json),
+        Err(PcpdError::Config(_))
+    ));
+    json["barrier correction"] = json!("none");
+    assert!(BarrierOption::from_json(&json).is_ok());
+
+    let json = barrier_json("up-and-out", "call", 120.0, "exact");
+    assert!(matches!(
+        BarrierOption::from_json(&json),
+        Err(PcpdError::Config(_))
+    ));
+
+    let mut json = read_json("data/basket/basket_2d/basket_2d.json");
+    json["option type"] = json!("barrier");
+    json["barrier type"] = json!("up-and-out");
+    json["barrier"] = json!(150.0);
+    json.as_object_mut().unwrap().remove("payoff coefficients");
+    assert!(matches!(
+        BarrierOption::from_json(&json),
+        Err(PcpdError::Config(_))
+    ));
+}
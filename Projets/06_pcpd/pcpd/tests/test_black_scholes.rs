@@ -0,0 +1,121 @@
+use ndarray::s;
+use pcpd::model::black_scholes::BlackScholesModel;
+use pcpd::model::dynamics::Model;
+
+mod common;
+use common::read_json;
+
+#[test]
+fn test_asset_starts_from_spots() {
+    let json = read_json("data/asian/asian.json");
+    let model = BlackScholesModel::from_json(&json).unwrap();
+
+    let path = model.asset(&mut rand::rng()).unwrap();
+
+    assert_eq!(path.nrows(), model.fixings_dates_number + 1);
+    assert_eq!(path.ncols(), model.model_size);
+    assert_eq!(path.row(0), model.spots);
+}
+
+#[test]
+fn test_asset_from_keeps_observed_fixings() {
+    let json = read_json("data/asian/asian.json");
+    let model = BlackScholesModel::from_json(&json).unwrap();
+    let full_path = model.asset(&mut rand::rng()).unwrap();
+
+    // t entre t_5 et t_6 : on observe S_{t_0}, ..., S_{t_5} puis S_t
+    let t = 5.5 * model.time_step;
+    let mut past = full_path.slice(s![..7, ..]).to_owned();
+    past.row_mut(6).fill(120.0);
+
+    let path = model.asset_from(&past, t, &mut rand::rng()).unwrap();
+
+    assert_eq!(path.nrows(), model.fixings_dates_number + 1);
+    assert_eq!(path.slice(s![..6, ..]), full_path.slice(s![..6, ..]));
+    assert_ne!(path.row(6), full_path.row(6));
+}
+
+#[test]
+fn test_asset_from_on_fixing_date() {
+    let json = read_json("data/asian/asian.json");
+    let model = BlackScholesModel::from_json(&json).unwrap();
+    let full_path = model.asset(&mut rand::rng()).unwrap();
+
+    // t = t_5 : le passé contient exactement S_{t_0}, ..., S_{t_5}
+    let t = 5.0 * model.time_step;
+    let past = full_path.slice(s![..6, ..]).to_owned();
+
+    let path = model.asset_from(&past, t, &mut rand::rng()).unwrap();
+
+    assert_eq!(model.last_fixing_index(t), 5);
+    assert_eq!(path.slice(s![..6, ..]), past);
+}
+
+#[test]
+fn test_shift_asset_only_moves_future_of_one_asset() {
+    let json = read_json("data/asian/asian.json");
+    let model = BlackScholesModel::from_json(&json).unwrap();
+    let path = model.asset(&mut rand::rng()).unwrap();
+
+    let t = 5.5 * model.time_step;
+    let shifted = model.shift_asset(&path, 1, 0.1, t);
+
+    assert_eq!(shifted.slice(s![..6, ..]), path.slice(s![..6, ..]));
+    assert_eq!(shifted.column(0), path.column(0));
+    for i in 6..path.nrows() {
+        assert!((shifted[[i, 1]] - 1.1 * path[[i, 1]]).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_dividends_lower_the_forward() {
+    let json = read_json("data/call/call.json");
+    let mut model = BlackScholesModel::from_json(&json).unwrap();
+    model.dividend_rate.fill(0.03);
+
+    // E[S_T] = S_0 e^{(r - q) T}
+    let m = 50000;
+    let mut rng = rand::rng();
+    let mean = (0..m)
+        .map(|_| model.asset(&mut rng).unwrap()[[model.fixings_dates_number, 0]])
+        .sum::<f64>()
+        / m as f64;
+    let expected = model.spots[0] * ((model.interest_rate - 0.03) * model.maturity).exp();
+
+    // écart-type de S_T proche de 20 : 5 écarts-types de la moyenne
+    assert!((mean - expected).abs() < 0.5, "{} vs {}", mean, expected);
+}
+
+#[test]
+fn test_simulate_market_follows_trend() {
+    let json = read_json("data/basket/basket_2d/basket_2d.json");
+    let mut model = BlackScholesModel::from_json(&json).unwrap();
+    let trend = ndarray::Array1::from(vec![0.1, -0.05]);
+
+    let market = model.simulate_market(&trend, 12, &mut rand::rng()).unwrap();
+    assert_eq!(market.nrows(), 13);
+    assert_eq!(market.ncols(), 2);
+    assert_eq!(market.row(0), model.spots);
+
+    // sans volatilité, S_{tau_k} = S_0 e^{(trend - q) tau_k}
+    model.volatility.fill(0.0);
+    model.dividend_rate.fill(0.02);
+    let market = model.simulate_market(&trend, 12, &mut rand::rng()).unwrap();
+    for k in 0..=12 {
+        let tau = k as f64 / 12.0 * model.maturity;
+        for j in 0..2 {
+            let expected = model.spots[j] * ((trend[j] - 0.02) * tau).exp();
+            assert!((market[[k, j]] - expected).abs() < 1e-9);
+        }
+    }
+
+    assert!(
+        model
+            .simulate_market(
+                &trend.slice(ndarray::s![..1]).to_owned(),
+                12,
+                &mut rand::rng()
+            )
+            .is_err()
+    );
+}
@@ -0,0 +1,99 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+fn run_pcpd(args: &[&str]) -> Value {
+    let output = Command::new(env!("CARGO_BIN_EXE_pcpd"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(args)
+        .output()
+        .expect("Impossible de lancer pcpd");
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("JSON invalide")
+}
+
+#[test]
+fn test_cli_price() {
+    let result = run_pcpd(&["price", "data/call/call.json", "--samples", "1000"]);
+
+    assert!(result["price"].as_f64().unwrap() > 0.0);
+    assert!(result["priceStdDev"].as_f64().unwrap() > 0.0);
+    assert_eq!(result["delta"].as_array().unwrap().len(), 1);
+    assert_eq!(result["deltaStdDev"].as_array().unwrap().len(), 1);
+    assert!(result["time"].is_number());
+}
+
+#[test]
+fn test_cli_price_with_seed_is_reproducible() {
+    let args = [
+        "price",
+        "data/asian/asian.json",
+        "--samples",
+        "500",
+        "--seed",
+        "42",
+    ];
+
+    let first = run_pcpd(&args);
+    let second = run_pcpd(&args);
+
+    assert_eq!(first["price"], second["price"]);
+    assert_eq!(first["delta"], second["delta"]);
+}
+
+#[test]
+fn test_cli_hedge() {
+    let result = run_pcpd(&[
+        "hedge",
+        "data/perf/perf.json",
+        "data/perf/perf_market.txt",
+        "--samples",
+        "200",
+    ]);
+
+    assert!(result["initialPrice"].as_f64().unwrap() > 1.0);
+    assert!(result["initialPriceStdDev"].is_number());
+    assert!(result["finalPnL"].is_number());
+    assert!(result["time"].is_number());
+}
+
+#[test]
+fn test_cli_unknown_file() {
+    let output = Command::new(env!("CARGO_BIN_EXE_pcpd"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["price", "data/missing.json"])
+        .output()
+        .expect("Impossible de lancer pcpd");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("data/missing.json"));
+}
+
+#[test]
+fn test_cli_market() {
+    let output = Command::new(env!("CARGO_BIN_EXE_pcpd"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args([
+            "market",
+            "data/basket/basket_2d/basket_2d.json",
+            "--seed",
+            "1",
+        ])
+        .output()
+        .expect("Impossible de lancer pcpd");
+
+    assert!(output.status.success());
+    let market = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = market.lines().collect();
+
+    // 7 lignes d'en-tête puis H + 1 lignes de prix
+    assert_eq!(lines.len(), 7 + 366);
+    assert_eq!(lines[0], "# maturity time: 1.000000");
+    assert_eq!(lines[2], "# number of dates: 365");
+    assert_eq!(lines[7], "100.000000 100.000000");
+}
@@ -0,0 +1,86 @@
+use pcpd::config::PricingConfig;
+
+mod common;
+use common::read_json;
+
+#[test]
+fn test_config_expands_single_values() {
+    let json = read_json("data/basket/basket_40d/basket_40d.json");
+
+    let config = PricingConfig::from_json(&json).unwrap();
+
+    assert_eq!(config.option_size, 40);
+    assert_eq!(config.spot, vec![100.0; 40]);
+    assert_eq!(config.volatility, vec![0.2; 40]);
+    assert_eq!(config.payoff_coefficients, vec![0.025; 40]);
+    assert_eq!(config.fixing_dates_number, 1);
+    assert_eq!(config.hedging_dates_number, 365);
+}
+
+#[test]
+fn test_config_without_strike_and_trend() {
+    let json = read_json("data/perf/perf.json");
+
+    let config = PricingConfig::from_json(&json).unwrap();
+
+    assert_eq!(config.option_type, "performance");
+    assert_eq!(config.strike, None);
+    assert!(config.trend.is_empty());
+}
+
+#[test]
+fn test_config_missing_key() {
+    let mut json = read_json("data/call/call.json");
+    json.as_object_mut().unwrap().remove("interest rate");
+
+    let error = PricingConfig::from_json(&json).unwrap_err().to_string();
+
+    assert!(error.contains("interest rate"), "{}", error);
+}
+
+#[test]
+fn test_config_wrong_vector_size() {
+    let data = r#"{
+        "model type": "bs", "option type": "basket", "option size": 3, "strike": 100.0,
+        "spot": [100.0, 90.0], "maturity": 1.0, "volatility": [0.2], "interest rate": 0.02,
+        "correlation": 0.0, "payoff coefficients": [0.5], "fixing dates number": 1,
+        "sample number": 1000, "hedging dates number": 10, "fd step": 0.1
+    }"#;
+
+    let error = data.parse::<PricingConfig>().unwrap_err().to_string();
+
+    assert!(error.contains("\"spot\" has 2 values"), "{}", error);
+}
+
+#[test]
+fn test_config_dividend_rate() {
+    let mut json = read_json("data/basket/basket_5d/basket_5d.json");
+    let config = PricingConfig::from_json(&json).unwrap();
+    assert!(config.dividend_rate.is_empty());
+
+    json["dividend rate"] = serde_json::json!([0.03]);
+    let config = PricingConfig::from_json(&json).unwrap();
+    assert_eq!(config.dividend_rate, vec![0.03; 5]);
+}
+
+#[test]
+fn test_config_unknown_key() {
+    let mut json = read_json("data/call/call.json");
+    json["dividend rates"] = serde_json::json!([0.03]);
+
+    let error = PricingConfig::from_json(&json).unwrap_err().to_string();
+
+    assert!(error.contains("dividend rates"), "{}", error);
+}
+
+#[test]
+fn test_config_zero_sizes() {
+    for key in ["option size", "fixing dates number"] {
+        let mut json = read_json("data/call/call.json");
+        json[key] = serde_json::json!(0);
+
+        let error = PricingConfig::from_json(&json).unwrap_err().to_string();
+
+        assert!(error.contains(key), "{}", error);
+    }
+}
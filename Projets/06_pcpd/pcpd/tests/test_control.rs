@@ -0,0 +1,196 @@
+use ndarray::{Array2, Axis, s};
+use pcpd::math::random::normal_cdf;
+use pcpd::mc::control::{ControlVariate, GeometricControl};
+use pcpd::mc::pricer::MonteCarlo;
+use pcpd::mc::rng::Xoshiro256PlusPlus;
+use pcpd::model::black_scholes::BlackScholesModel;
+use pcpd::options::factory::option_from_config;
+use rand::SeedableRng;
+
+mod common;
+use common::config;
+
+// moyenne et écart-type de l'estimateur Monte Carlo de E[C | F_t]
+fn simulated_expectation(
+    control: &GeometricControl,
+    model: &BlackScholesModel,
+    past: &Array2<f64>,
+    t: f64,
+) -> (f64, f64) {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(11);
+    let m = 50000;
+    let (mut sum, mut sum_sq) = (0.0, 0.0);
+    for _ in 0..m {
+        let payoff = control
+            .payoff(&model.asset_from(past, t, &mut rng).unwrap())
+            .unwrap();
+        sum += payoff;
+        sum_sq += payoff * payoff;
+    }
+
+    let mean = sum / m as f64;
+    (mean, ((sum_sq / m as f64 - mean * mean) / m as f64).sqrt())
+}
+
+#[test]
+fn test_geometric_basket_of_one_asset_is_black_scholes_call() {
+    let config = config("data/call/call.json");
+    let model = BlackScholesModel::from_config(&config).unwrap();
+    let control = GeometricControl::from_config(&config).unwrap();
+    let past = model.spots.clone().insert_axis(Axis(0));
+
+    let (s0, k, r, sigma, t): (f64, f64, f64, f64, f64) =
+        (100.0, 100.0, model.interest_rate, 0.2, model.maturity);
+    let d1 = (s0 / k).ln() + (r + 0.5 * sigma * sigma) * t;
+    let d1 = d1 / (sigma * t.sqrt());
+    let d2 = d1 - sigma * t.sqrt();
+    let call = s0 * normal_cdf(d1) - k * (-r * t).exp() * normal_cdf(d2);
+
+    let expectation = control.expectation(&model, &past, 0.0).unwrap();
+
+    assert!(((-r * t).exp() * expectation - call).abs() < 1e-10);
+}
+
+#[test]
+fn test_geometric_asian_expectation() {
+    let config = config("data/asian/asian.json");
+    let model = BlackScholesModel::from_config(&config).unwrap();
+    let control = GeometricControl::from_config(&config).unwrap();
+    let past = model.spots.clone().insert_axis(Axis(0));
+
+    let expectation = control.expectation(&model, &past, 0.0).unwrap();
+    let (mean, std_dev) = simulated_expectation(&control, &model, &past, 0.0);
+
+    assert!(
+        (expectation - mean).abs() < 5.0 * std_dev,
+        "closed form {} vs simulated {} +- {}",
+        expectation,
+        mean,
+        std_dev
+    );
+}
+
+#[test]
+fn test_geometric_basket_expectation_with_correlation() {
+    let mut config = config("data/basket/basket_5d/basket_5d.json");
+    config.correlation = 0.5;
+    let model = BlackScholesModel::from_config(&config).unwrap();
+    let control = GeometricControl::from_config(&config).unwrap();
+    let past = model.spots.clone().insert_axis(Axis(0));
+
+    let expectation = control.expectation(&model, &past, 0.0).unwrap();
+    let (mean, std_dev) = simulated_expectation(&control, &model, &past, 0.0);
+
+    assert!((expectation - mean).abs() < 5.0 * std_dev);
+}
+
+#[test]
+fn test_geometric_asian_conditional_expectation() {
+    let config = config("data/asian/asian.json");
+    let model = BlackScholesModel::from_config(&config).unwrap();
+    let control = GeometricControl::from_config(&config).unwrap();
+    let path = model
+        .asset(&mut Xoshiro256PlusPlus::seed_from_u64(3))
+        .unwrap();
+
+    // entre t_10 et t_11 : fixings t_0, ..., t_10 puis S_t
+    let t = 10.5 * model.time_step;
+    let past = path.slice(s![..12, ..]).to_owned();
+
+    let expectation = control.expectation(&model, &past, t).unwrap();
+    let (mean, std_dev) = simulated_expectation(&control, &model, &past, t);
+    assert!((expectation - mean).abs() < 5.0 * std_dev);
+
+    // en T, tout est observé
+    let at_maturity = control.expectation(&model, &path, model.maturity).unwrap();
+    assert!((at_maturity - control.payoff(&path).unwrap()).abs() < 1e-12);
+}
+
+#[test]
+fn test_controlled_price_of_asian() {
+    let config = config("data/asian/asian.json");
+    let option = option_from_config(&config).unwrap();
+    let mut pricer = MonteCarlo::from_config(&config, option).unwrap();
+    pricer.control = Some(Box::new(GeometricControl::from_config(&config).unwrap()));
+
+    let result = pricer
+        .price(&mut Xoshiro256PlusPlus::seed_from_u64(21))
+        .unwrap();
+    let control = result.control.unwrap();
+
+    // le prix attendu, à 5 écarts-types
+    assert!((result.price - 4.6230359733474184).abs() < 5.0 * 0.029779756412935725);
+    assert!(control.coefficient > 0.5);
+    // la moyenne géométrique explique l'essentiel de la variance
+    assert!(
+        result.price_std_dev < 0.2 * control.raw_price_std_dev,
+        "{} vs {}",
+        result.price_std_dev,
+        control.raw_price_std_dev
+    );
+    assert!((control.raw_price - result.price).abs() < 5.0 * control.raw_price_std_dev);
+}
+
+#[test]
+fn test_controlled_price_of_call_is_exact() {
+    // pour un seul actif à une date, le contrôle est l'option elle-même
+    let config = config("data/call/call.json");
+    let option = option_from_config(&config).unwrap();
+    let mut pricer = MonteCarlo::from_config(&config, option).unwrap();
+    pricer.sample_number = 2000;
+    pricer.control = Some(Box::new(GeometricControl::from_config(&config).unwrap()));
+
+    let model = BlackScholesModel::from_config(&config).unwrap();
+    let past = model.spots.clone().insert_axis(Axis(0));
+    let discount = (-model.interest_rate * model.maturity).exp();
+    let exact = discount
+        * pricer
+            .control
+            .as_ref()
+            .unwrap()
+            .expectation(&model, &past, 0.0)
+            .unwrap();
+
+    let result = pricer
+        .price(&mut Xoshiro256PlusPlus::seed_from_u64(5))
+        .unwrap();
+
+    assert!((result.price - exact).abs() < 1e-8);
+    assert!(result.price_std_dev < 1e-6);
+    assert!((result.control.unwrap().coefficient - 1.0).abs() < 1e-8);
+}
+
+#[test]
+fn test_pricing_result_reports_raw_price() {
+    let config = config("data/basket/basket_5d/basket_5d.json");
+    let option = option_from_config(&config).unwrap();
+    let mut pricer = MonteCarlo::from_config(&config, option).unwrap();
+    pricer.sample_number = 2000;
+
+    let plain = pricer
+        .price_and_delta(&mut Xoshiro256PlusPlus::seed_from_u64(1))
+        .unwrap();
+    let json = serde_json::to_value(&plain).unwrap();
+    assert!(json.get("rawPrice").is_none());
+
+    pricer.control = Some(Box::new(GeometricControl::from_config(&config).unwrap()));
+    let controlled = pricer
+        .price_and_delta(&mut Xoshiro256PlusPlus::seed_from_u64(1))
+        .unwrap();
+    let json = serde_json::to_value(&controlled).unwrap();
+
+    // mêmes trajectoires : l'estimation brute est celle sans contrôle
+    assert_eq!(json["rawPrice"].as_f64().unwrap(), plain.price);
+    assert_eq!(
+        json["rawPriceStdDev"].as_f64().unwrap(),
+        plain.price_std_dev
+    );
+    assert!(json["controlCoefficient"].is_number());
+    assert_eq!(controlled.delta, plain.delta);
+}
+
+#[test]
+fn test_no_control_for_performance() {
+    let config = config("data/perf/perf.json");
+    assert!(GeometricControl::from_config(&config).is_err());
+}
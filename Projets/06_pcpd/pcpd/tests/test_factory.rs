@@ -0,0 +1,71 @@
+use pcpd::error::PcpdError;
+use pcpd::model::factory::model_from_json;
+use pcpd::options::factory::option_from_json;
+
+mod common;
+use common::read_json;
+
+#[test]
+fn test_factory_builds_every_data_option() {
+    let paths = [
+        "data/call/call.json",
+        "data/asian/asian.json",
+        "data/perf/perf.json",
+        "data/basket/basket_2d/basket_2d.json",
+        "data/basket/basket_5d/basket_5d.json",
+        "data/basket/basket_5d_1/basket_5d_1.json",
+        "data/basket/basket_40d/basket_40d.json",
+        "data/barrier/barrier.json",
+    ];
+
+    for path in paths {
+        assert!(option_from_json(&read_json(path)).is_ok(), "{}", path);
+    }
+}
+
+#[test]
+fn test_factory_call_and_put() {
+    let mut json = read_json("data/call/call.json");
+    let path = ndarray::array![[100.0], [110.0]];
+
+    json["option type"] = serde_json::json!("call");
+    let call = option_from_json(&json).unwrap();
+    assert_eq!(call.payoff(&path).unwrap(), 10.0);
+
+    json["option type"] = serde_json::json!("put");
+    let put = option_from_json(&json).unwrap();
+    assert_eq!(put.payoff(&path).unwrap(), 0.0);
+}
+
+#[test]
+fn test_factory_unknown_option_type() {
+    let mut json = read_json("data/call/call.json");
+    json["option type"] = serde_json::json!("lookback");
+
+    let result = option_from_json(&json);
+
+    assert!(matches!(result, Err(PcpdError::UnknownOptionType(t)) if t == "lookback"));
+}
+
+#[test]
+fn test_factory_missing_strike() {
+    let mut json = read_json("data/perf/perf.json");
+    json["option type"] = serde_json::json!("basket");
+
+    let result = option_from_json(&json);
+
+    assert!(matches!(result, Err(PcpdError::Config(_))));
+}
+
+#[test]
+fn test_model_factory() {
+    let mut json = read_json("data/basket/basket_5d/basket_5d.json");
+
+    let model = model_from_json(&json).unwrap();
+    assert_eq!(model.model_size(), 5);
+    assert!(model.as_black_scholes().is_some());
+
+    json["model type"] = serde_json::json!("sabr");
+    let result = model_from_json(&json);
+    assert!(matches!(result, Err(PcpdError::UnknownModelType(t)) if t == "sabr"));
+}
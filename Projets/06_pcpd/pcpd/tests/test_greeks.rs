@@ -0,0 +1,149 @@
+use pcpd::config::PricingConfig;
+use pcpd::math::random::normal_pdf;
+use pcpd::mc::greeks::{GreeksMethod, GreeksResult};
+use pcpd::mc::pricer::MonteCarlo;
+use pcpd::mc::rng::Xoshiro256PlusPlus;
+use pcpd::model::analytic::AnalyticVanilla;
+use pcpd::options::digital::DigitalOption;
+use pcpd::options::factory::option_from_config;
+use rand::SeedableRng;
+
+mod common;
+use common::config;
+
+fn greeks(config: &PricingConfig, method: GreeksMethod, seed: u64) -> GreeksResult {
+    let option = option_from_config(config).unwrap();
+    let pricer = MonteCarlo::from_config(config, option).unwrap();
+
+    pricer
+        .greeks(method, &mut Xoshiro256PlusPlus::seed_from_u64(seed))
+        .unwrap()
+}
+
+fn assert_within(name: &str, value: f64, std_dev: f64, expected: f64) {
+    assert!(
+        (value - expected).abs() < 5.0 * std_dev,
+        "{} {} +- {} vs {}",
+        name,
+        value,
+        std_dev,
+        expected
+    );
+}
+
+#[test]
+fn test_call_greeks_match_closed_form() {
+    let config = config("data/call/call.json");
+    let analytic = AnalyticVanilla::from_config(&config).unwrap().greeks();
+
+    for method in [GreeksMethod::Pathwise, GreeksMethod::LikelihoodRatio] {
+        let result = greeks(&config, method, 1);
+
+        assert_within(
+            "delta",
+            result.delta[0],
+            result.delta_std_dev[0],
+            analytic.delta,
+        );
+        assert_within(
+            "gamma",
+            result.gamma[0],
+            result.gamma_std_dev[0],
+            analytic.gamma,
+        );
+        assert_within(
+            "vega",
+            result.vega[0],
+            result.vega_std_dev[0],
+            analytic.vega,
+        );
+    }
+}
+
+#[test]
+fn test_pathwise_is_less_noisy_than_likelihood_ratio() {
+    let config = config("data/call/call.json");
+
+    let pathwise = greeks(&config, GreeksMethod::Pathwise, 2);
+    let lr = greeks(&config, GreeksMethod::LikelihoodRatio, 2);
+
+    assert!(pathwise.delta_std_dev[0] < lr.delta_std_dev[0]);
+    assert!(pathwise.vega_std_dev[0] < lr.vega_std_dev[0]);
+}
+
+#[test]
+fn test_digital_delta_by_likelihood_ratio() {
+    let config = config("data/call/call.json");
+    let pricer = MonteCarlo::from_config(&config, Box::new(DigitalOption::new(100.0))).unwrap();
+
+    // delta du digital : e^{-rT} phi(d2) / (S sigma sqrt(T))
+    let (s, k, r, sigma, t): (f64, f64, f64, f64, f64) =
+        (100.0, 100.0, config.interest_rate, 0.2, 1.0);
+    let d2 = ((s / k).ln() + (r - 0.5 * sigma * sigma) * t) / (sigma * t.sqrt());
+    let expected = (-r * t).exp() * normal_pdf(d2) / (s * sigma * t.sqrt());
+
+    let result = pricer
+        .greeks(
+            GreeksMethod::LikelihoodRatio,
+            &mut Xoshiro256PlusPlus::seed_from_u64(3),
+        )
+        .unwrap();
+    assert_within("delta", result.delta[0], result.delta_std_dev[0], expected);
+
+    // pas de gradient pour un payoff discontinu
+    assert!(
+        pricer
+            .greeks(
+                GreeksMethod::Pathwise,
+                &mut Xoshiro256PlusPlus::seed_from_u64(3)
+            )
+            .is_err()
+    );
+}
+
+#[test]
+fn test_correlated_basket_estimators_agree() {
+    let mut config = config("data/basket/basket_5d/basket_5d.json");
+    config.correlation = 0.5;
+    config.sample_number = 20000;
+
+    let pathwise = greeks(&config, GreeksMethod::Pathwise, 4);
+    let lr = greeks(&config, GreeksMethod::LikelihoodRatio, 5);
+
+    for j in 0..5 {
+        let delta_std_dev = pathwise.delta_std_dev[j].hypot(lr.delta_std_dev[j]);
+        assert_within("delta", pathwise.delta[j], delta_std_dev, lr.delta[j]);
+
+        let vega_std_dev = pathwise.vega_std_dev[j].hypot(lr.vega_std_dev[j]);
+        assert_within("vega", pathwise.vega[j], vega_std_dev, lr.vega[j]);
+
+        let gamma_std_dev = pathwise.gamma_std_dev[j].hypot(lr.gamma_std_dev[j]);
+        assert_within("gamma", pathwise.gamma[j], gamma_std_dev, lr.gamma[j]);
+    }
+}
+
+#[test]
+fn test_pathwise_delta_of_asian_matches_finite_differences() {
+    let config = config("data/asian/asian.json");
+    let result = greeks(&config, GreeksMethod::Pathwise, 6);
+
+    let expected = [0.2797678159697452, 0.2799045048149051];
+    let expected_std_dev = 0.001053998113230667;
+    for (j, expected) in expected.into_iter().enumerate() {
+        let std_dev = result.delta_std_dev[j].hypot(expected_std_dev);
+        assert_within("delta", result.delta[j], std_dev, expected);
+    }
+}
+
+#[test]
+fn test_greeks_method_from_str() {
+    assert_eq!(
+        "pathwise".parse::<GreeksMethod>().unwrap(),
+        GreeksMethod::Pathwise
+    );
+    assert_eq!(
+        "likelihood-ratio".parse::<GreeksMethod>().unwrap(),
+        GreeksMethod::LikelihoodRatio
+    );
+    assert!("finite-difference".parse::<GreeksMethod>().is_err());
+}
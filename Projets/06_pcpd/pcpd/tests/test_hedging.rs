@@ -0,0 +1,119 @@
+use ndarray::Array2;
+use pcpd::config::PricingConfig;
+use pcpd::error::PcpdError;
+use pcpd::market::MarketData;
+use pcpd::mc::hedging::Hedger;
+use pcpd::mc::pricer::MonteCarlo;
+use pcpd::mc::rng::Xoshiro256PlusPlus;
+use pcpd::model::black_scholes::BlackScholesModel;
+use pcpd::options::asian::AsianOption;
+use pcpd::options::call::CallOption;
+use pcpd::options::factory::option_from_config;
+use rand::SeedableRng;
+
+mod common;
+use common::read_json;
+
+fn read_market(path: &str) -> Array2<f64> {
+    MarketData::from_file(&format!("{}/{}", env!("CARGO_MANIFEST_DIR"), path))
+        .unwrap()
+        .prices
+}
+
+#[test]
+fn test_past_from_market() {
+    let json = read_json("data/asian/asian.json");
+    let model = BlackScholesModel::from_json(&json).unwrap();
+    let option = AsianOption::from_json(&json).unwrap();
+    let pricer = MonteCarlo::new(model, Box::new(option), 100, 0.1);
+    let hedger = Hedger::new(pricer, 360).unwrap();
+    let market = read_market("data/asian/asian_market.txt");
+
+    // 24 fixings pour 360 dates de couverture : un fixing toutes les 15 dates
+    let past = hedger.past_from_market(&market, 20);
+    assert_eq!(past.nrows(), 3);
+    assert_eq!(past.row(0), market.row(0));
+    assert_eq!(past.row(1), market.row(15));
+    assert_eq!(past.row(2), market.row(20));
+
+    let past = hedger.past_from_market(&market, 30);
+    assert_eq!(past.nrows(), 3);
+    assert_eq!(past.row(2), market.row(30));
+}
+
+#[test]
+fn test_hedge_call() {
+    let json = read_json("data/call/call.json");
+    let model = BlackScholesModel::from_json(&json).unwrap();
+    let option = CallOption::new(json["strike"].as_f64().unwrap());
+    let pricer = MonteCarlo::new(model, Box::new(option), 5000, 0.1);
+    let hedger = Hedger::new(pricer, 365).unwrap();
+    let market = read_market("data/call/call_market.txt");
+
+    let result = hedger
+        .hedge(&market, &mut Xoshiro256PlusPlus::seed_from_u64(0))
+        .unwrap();
+
+    // le P&L contient V_0 e^{rT} : l'écart au P&L attendu est dominé par l'erreur Monte Carlo
+    // des deux prix initiaux, le bruit des deltas étant bien plus faible
+    let expected = read_json("data/call/call_expected_hedge.json");
+    let expected_pnl = expected["finalPnL"].as_f64().unwrap();
+    let expected_std_dev = expected["initialPriceStdDev"].as_f64().unwrap();
+    let capitalisation =
+        (json["interest rate"].as_f64().unwrap() * json["maturity"].as_f64().unwrap()).exp();
+    let pnl_std_dev =
+        capitalisation * (result.initial_price_std_dev.powi(2) + expected_std_dev.powi(2)).sqrt();
+
+    assert!(
+        (result.final_pnl - expected_pnl).abs() < 4.0 * pnl_std_dev,
+        "final P&L {} (price {}) too far from expected {} +- {}",
+        result.final_pnl,
+        result.initial_price,
+        expected_pnl,
+        pnl_std_dev
+    );
+}
+
+#[test]
+fn test_hedge_credits_dividends() {
+    // sans volatilité, delta = e^{-qT} pour un call très dans la monnaie : les dividendes reçus
+    // compensent exactement la dérive moindre du sous-jacent
+    let json = read_json("data/call/call.json");
+    let mut model = BlackScholesModel::from_json(&json).unwrap();
+    model.volatility.fill(0.0);
+    model.dividend_rate.fill(0.03);
+    let (r, q, t) = (model.interest_rate, 0.03, model.maturity);
+
+    let h = 10;
+    let market = Array2::from_shape_fn((h + 1, 1), |(i, _)| {
+        100.0 * ((r - q) * t * i as f64 / h as f64).exp()
+    });
+    let pricer = MonteCarlo::new(model, Box::new(CallOption::new(50.0)), 10, 0.1);
+    let hedger = Hedger::new(pricer, h).unwrap();
+
+    let result = hedger
+        .hedge(&market, &mut Xoshiro256PlusPlus::seed_from_u64(3))
+        .unwrap();
+
+    assert!(result.final_pnl.abs() < 1e-6, "{}", result.final_pnl);
+}
+
+#[test]
+fn test_hedging_dates_must_contain_fixing_dates() {
+    for (fixings, hedging) in [(7, 360), (24, 0)] {
+        let mut json = read_json("data/asian/asian.json");
+        json["fixing dates number"] = serde_json::json!(fixings);
+        json["hedging dates number"] = serde_json::json!(hedging);
+        let config = PricingConfig::from_json(&json).unwrap();
+        let option = option_from_config(&config).unwrap();
+
+        let error = Hedger::from_config(&config, option).err().unwrap();
+
+        assert!(matches!(error, PcpdError::Config(_)), "{}", error);
+        assert!(
+            error.to_string().contains("hedging dates number"),
+            "{}",
+            error
+        );
+    }
+}
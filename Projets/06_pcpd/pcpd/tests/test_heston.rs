@@ -0,0 +1,207 @@
+use pcpd::config::PricingConfig;
+use pcpd::error::PcpdError;
+use pcpd::mc::pricer::MonteCarlo;
+use pcpd::mc::rng::Xoshiro256PlusPlus;
+use pcpd::model::analytic::{AnalyticVanilla, VanillaKind};
+use pcpd::model::dynamics::Model;
+use pcpd::model::heston::HestonModel;
+use pcpd::options::call::CallOption;
+use pcpd::options::factory::option_from_config;
+use pcpd::options::put::PutOption;
+use rand::SeedableRng;
+use serde_json::{Value, json};
+
+mod common;
+use common::read_json;
+
+// paramètres de Heston ajoutés à une configuration Black-Scholes
+fn heston_json(path: &str, xi: f64, rho: f64) -> Value {
+    let mut json = read_json(path);
+    json["model type"] = json!("heston");
+    json["kappa"] = json!([2.0]);
+    json["theta"] = json!([0.04]);
+    json["xi"] = json!([xi]);
+    json["v0"] = json!([0.04]);
+    json["spot vol correlation"] = json!([rho]);
+    json["euler steps"] = json!(50);
+    json
+}
+
+fn heston_price(json: &Value, kind: VanillaKind, strike: f64, seed: u64) -> (f64, f64) {
+    let model = HestonModel::from_json(json).unwrap();
+    let option: Box<dyn pcpd::options::option::Option> = match kind {
+        VanillaKind::Call => Box::new(CallOption::new(strike)),
+        VanillaKind::Put => Box::new(PutOption::new(strike)),
+    };
+    let pricer = MonteCarlo::new(model, option, 50000, 0.1);
+
+    let result = pricer
+        .price(&mut Xoshiro256PlusPlus::seed_from_u64(seed))
+        .unwrap();
+    (result.price, result.price_std_dev)
+}
+
+// volatilité implicite de Black-Scholes par dichotomie (S = 100, T = 1)
+fn implied_volatility(kind: VanillaKind, strike: f64, r: f64, price: f64) -> f64 {
+    let (mut low, mut high) = (1e-4, 2.0);
+    for _ in 0..100 {
+        let sigma = 0.5 * (low + high);
+        let vanilla = AnalyticVanilla::new(kind, 100.0, strike, 1.0, r, 0.0, sigma);
+        if vanilla.price() > price {
+            high = sigma;
+        } else {
+            low = sigma;
+        }
+    }
+    0.5 * (low + high)
+}
+
+#[test]
+fn test_constant_variance_is_black_scholes() {
+    // xi = 0 et v0 = theta : variance constante, sigma = 0.2
+    let json = heston_json("data/call/call.json", 0.0, -0.5);
+    let (price, std_dev) = heston_price(&json, VanillaKind::Call, 100.0, 1);
+
+    let r = json["interest rate"].as_f64().unwrap();
+    let expected = AnalyticVanilla::new(VanillaKind::Call, 100.0, 100.0, 1.0, r, 0.0, 0.2).price();
+    assert!(
+        (price - expected).abs() < 5.0 * std_dev,
+        "{} +- {} vs {}",
+        price,
+        std_dev,
+        expected
+    );
+}
+
+#[test]
+fn test_discounted_spot_is_martingale() {
+    let mut json = heston_json("data/call/call.json", 1.0, -0.7);
+    json["dividend rate"] = json!([0.02]);
+    json["fixing dates number"] = json!(4);
+    let model = HestonModel::from_json(&json).unwrap();
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(2);
+
+    let m = 50000;
+    let finals: Vec<f64> = (0..m)
+        .map(|_| model.asset(&mut rng).unwrap()[[4, 0]])
+        .collect();
+    let mean = finals.iter().sum::<f64>() / m as f64;
+    let variance = finals.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / m as f64;
+
+    let r = json["interest rate"].as_f64().unwrap();
+    let expected = 100.0 * (r - 0.02).exp();
+    assert!((mean - expected).abs() < 5.0 * (variance / m as f64).sqrt());
+}
+
+#[test]
+fn test_negative_correlation_gives_downward_skew() {
+    let json = heston_json("data/call/call.json", 0.6, -0.7);
+    let r = json["interest rate"].as_f64().unwrap();
+
+    let (put, _) = heston_price(&json, VanillaKind::Put, 80.0, 3);
+    let (call, _) = heston_price(&json, VanillaKind::Call, 120.0, 4);
+
+    let low_strike = implied_volatility(VanillaKind::Put, 80.0, r, put);
+    let high_strike = implied_volatility(VanillaKind::Call, 120.0, r, call);
+    assert!(
+        low_strike > high_strike + 0.02,
+        "implied volatilities {} (K = 80) and {} (K = 120)",
+        low_strike,
+        high_strike
+    );
+}
+
+#[test]
+fn test_existing_payoffs_under_heston() {
+    for path in [
+        "data/asian/asian.json",
+        "data/perf/perf.json",
+        "data/basket/basket_5d/basket_5d.json",
+    ] {
+        let json = heston_json(path, 0.3, -0.5);
+        let config = PricingConfig::from_json(&json).unwrap();
+        let option = option_from_config(&config).unwrap();
+        let mut pricer = MonteCarlo::from_config(&config, option).unwrap();
+        pricer.sample_number = 2000;
+
+        let path_shape = pricer
+            .model
+            .asset(&mut Xoshiro256PlusPlus::seed_from_u64(5))
+            .unwrap()
+            .dim();
+        assert_eq!(
+            path_shape,
+            (config.fixing_dates_number + 1, config.option_size)
+        );
+
+        let result = pricer
+            .price_and_delta(&mut Xoshiro256PlusPlus::seed_from_u64(5))
+            .unwrap();
+        assert!(result.price > 0.0 && result.price.is_finite(), "{}", path);
+
+        // 2 gaussiennes par actif et par pas d'Euler : trop pour la suite de Sobol
+        let error = pricer
+            .price_and_delta_rqmc(4, &mut Xoshiro256PlusPlus::seed_from_u64(6))
+            .unwrap_err();
+        assert!(matches!(error, PcpdError::Config(_)), "{}", path);
+    }
+}
+
+#[test]
+fn test_rqmc_under_heston() {
+    let mut json = heston_json("data/call/call.json", 0.3, -0.5);
+    json["euler steps"] = json!(20);
+    let config = PricingConfig::from_json(&json).unwrap();
+    let option = option_from_config(&config).unwrap();
+    let mut pricer = MonteCarlo::from_config(&config, option).unwrap();
+    pricer.sample_number = 2000;
+    assert_eq!(pricer.model.gaussians_number(0.0), 40);
+
+    let rqmc = pricer
+        .price_and_delta_rqmc(4, &mut Xoshiro256PlusPlus::seed_from_u64(6))
+        .unwrap();
+    assert!(rqmc.price > 0.0 && rqmc.price.is_finite());
+}
+
+#[test]
+fn test_asset_from_rejects_positive_times() {
+    let mut json = heston_json("data/asian/asian.json", 0.5, -0.5);
+    json["kappa"] = json!([2.0, 1.0]);
+    let model = HestonModel::from_json(&json).unwrap();
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(7);
+    let full_path = model.asset(&mut rng).unwrap();
+    assert_eq!(model.kappa.to_vec(), vec![2.0, 1.0]);
+
+    // la variance en t n'est pas observée : pas de prix conditionnel en cours de vie
+    let t = 5.5 * model.time_step;
+    let past = full_path.slice(ndarray::s![..7, ..]).to_owned();
+    assert!(matches!(
+        model.asset_from(&past, t, &mut rng),
+        Err(PcpdError::Model(_))
+    ));
+
+    let config = PricingConfig::from_json(&json).unwrap();
+    let option = option_from_config(&config).unwrap();
+    let pricer = MonteCarlo::from_config(&config, option).unwrap();
+    assert!(pricer.price_at(&past, t, &mut rng).is_err());
+}
+
+#[test]
+fn test_heston_config_errors() {
+    let mut json = heston_json("data/call/call.json", 0.3, -0.5);
+    json.as_object_mut().unwrap().remove("kappa");
+    let error = HestonModel::from_json(&json).err().unwrap().to_string();
+    assert!(error.contains("kappa"), "{}", error);
+
+    let json = heston_json("data/call/call.json", 0.3, -1.5);
+    assert!(matches!(
+        HestonModel::from_json(&json),
+        Err(PcpdError::Model(_))
+    ));
+
+    // v0 = volatility^2 par défaut
+    let mut json = heston_json("data/call/call.json", 0.3, -0.5);
+    json.as_object_mut().unwrap().remove("v0");
+    let model = HestonModel::from_json(&json).unwrap();
+    assert!((model.v0[0] - 0.04).abs() < 1e-15);
+}
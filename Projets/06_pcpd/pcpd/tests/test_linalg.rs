@@ -0,0 +1,93 @@
+use approx::assert_abs_diff_eq;
+use ndarray::{Array2, array};
+use pcpd::error::PcpdError;
+use pcpd::math::linalg::{LinalgError, cholesky, invert_lower_triangular};
+use pcpd::model::black_scholes::BlackScholesModel;
+
+mod common;
+use common::read_json;
+
+#[test]
+fn test_cholesky_reconstructs_matrix() {
+    let a = array![[4.0, 2.0, 0.4], [2.0, 5.0, 1.0], [0.4, 1.0, 3.0]];
+
+    let l = cholesky(&a).unwrap();
+
+    let diff = l.dot(&l.t()) - &a;
+    assert!(diff.iter().all(|x| x.abs() < 1e-12));
+    assert_eq!(l[[0, 1]], 0.0);
+    assert_eq!(l[[0, 2]], 0.0);
+    assert_eq!(l[[1, 2]], 0.0);
+}
+
+#[test]
+fn test_cholesky_of_identity() {
+    let a = Array2::<f64>::eye(3);
+
+    assert_eq!(cholesky(&a).unwrap(), a);
+}
+
+#[test]
+fn test_cholesky_not_positive_definite() {
+    let a = array![[1.0, 2.0], [2.0, 1.0]];
+
+    assert!(matches!(
+        cholesky(&a),
+        Err(LinalgError::NotPositiveDefinite { index: 1, .. })
+    ));
+}
+
+#[test]
+fn test_cholesky_not_square() {
+    let a = Array2::<f64>::zeros((2, 3));
+
+    assert_eq!(
+        cholesky(&a),
+        Err(LinalgError::NotSquare { rows: 2, cols: 3 })
+    );
+}
+
+#[test]
+fn test_model_uses_cholesky_of_correlation() {
+    let json = read_json("data/basket/basket_40d/basket_40d.json");
+    let model = BlackScholesModel::from_json(&json).unwrap();
+
+    let correlation = model.l.dot(&model.l.t());
+
+    assert_abs_diff_eq!(correlation[[0, 0]], 1.0, epsilon = 1e-12);
+    assert_abs_diff_eq!(correlation[[3, 17]], 0.7, epsilon = 1e-12);
+}
+
+#[test]
+fn test_model_rejects_invalid_correlation() {
+    let mut json = read_json("data/basket/basket_5d/basket_5d.json");
+    json["correlation"] = serde_json::json!(-0.5);
+
+    let result = BlackScholesModel::from_json(&json);
+
+    let error = result.err().unwrap();
+    assert!(matches!(error, PcpdError::InvalidCorrelation { .. }));
+    assert!(error.to_string().contains("invalid correlation -0.5"));
+}
+
+#[test]
+fn test_invert_lower_triangular() {
+    let a = array![[4.0, 2.0, 0.4], [2.0, 5.0, 1.0], [0.4, 1.0, 3.0]];
+    let l = cholesky(&a).unwrap();
+
+    let inverse = invert_lower_triangular(&l).unwrap();
+
+    let diff = inverse.dot(&l) - Array2::<f64>::eye(3);
+    assert!(diff.iter().all(|x| x.abs() < 1e-12));
+    assert_eq!(inverse[[0, 1]], 0.0);
+}
+
+#[test]
+fn test_invert_singular_lower_triangular() {
+    let l = array![[1.0, 0.0], [2.0, 0.0]];
+
+    assert_eq!(
+        invert_lower_triangular(&l),
+        Err(LinalgError::Singular { index: 1 })
+    );
+}
@@ -0,0 +1,156 @@
+use pcpd::config::PricingConfig;
+use pcpd::market::MarketData;
+use pcpd::model::black_scholes::BlackScholesModel;
+
+mod common;
+use common::read_json;
+
+const CASES: [&str; 7] = [
+    "asian/asian",
+    "call/call",
+    "perf/perf",
+    "basket/basket_2d/basket_2d",
+    "basket/basket_5d/basket_5d",
+    "basket/basket_5d_1/basket_5d_1",
+    "basket/basket_40d/basket_40d",
+];
+
+fn market_path(path: &str) -> String {
+    format!("{}/{}", env!("CARGO_MANIFEST_DIR"), path)
+}
+
+const SMALL_MARKET: &str = "# maturity time: 1.000000
+# model size: 2
+# number of dates: 2
+# spot: 100.000000 90.000000
+# dividend rate: 0.000000 0.010000
+# correlation: 0.500000
+# volatility: 0.200000 0.300000
+100.000000 90.000000
+101.000000 89.000000
+102.500000 91.250000
+";
+
+fn parse_error(data: &str) -> String {
+    data.parse::<MarketData>().unwrap_err().to_string()
+}
+
+#[test]
+fn test_read_market_file() {
+    let market = MarketData::from_file(&market_path("data/asian/asian_market.txt")).unwrap();
+
+    assert_eq!(market.model_size, 2);
+    assert_eq!(market.dates_number, 360);
+    assert_eq!(market.prices.nrows(), 361);
+    assert_eq!(market.prices.ncols(), 2);
+    assert_eq!(market.prices[[0, 0]], 100.0);
+    assert_eq!(market.prices[[1, 1]], 99.422643);
+}
+
+#[test]
+fn test_parse_headers() {
+    let market: MarketData = SMALL_MARKET.parse().unwrap();
+
+    assert_eq!(market.maturity, 1.0);
+    assert_eq!(market.model_size, 2);
+    assert_eq!(market.dates_number, 2);
+    assert_eq!(market.spot.to_vec(), vec![100.0, 90.0]);
+    assert_eq!(market.dividend_rate.to_vec(), vec![0.0, 0.01]);
+    assert_eq!(market.correlation, 0.5);
+    assert_eq!(market.volatility.to_vec(), vec![0.2, 0.3]);
+    assert_eq!(market.prices[[2, 1]], 91.25);
+}
+
+#[test]
+fn test_data_files_round_trip() {
+    for case in CASES {
+        let path = market_path(&format!("data/{}_market.txt", case));
+        let data = std::fs::read_to_string(&path).unwrap();
+        let market: MarketData = data.parse().unwrap();
+
+        assert_eq!(market.to_string(), data, "{}", case);
+    }
+}
+
+#[test]
+fn test_market_matches_configs() {
+    for case in CASES {
+        let config = PricingConfig::from_json(&read_json(format!("data/{}.json", case))).unwrap();
+        let market =
+            MarketData::from_file(&market_path(&format!("data/{}_market.txt", case))).unwrap();
+
+        market.check_config(&config).unwrap();
+    }
+}
+
+#[test]
+fn test_market_mismatch_with_config() {
+    let mut config = PricingConfig::from_json(&read_json("data/call/call.json")).unwrap();
+    let market = MarketData::from_file(&market_path("data/call/call_market.txt")).unwrap();
+
+    config.dividend_rate = vec![0.03];
+    let error = market.check_config(&config).unwrap_err().to_string();
+    assert!(error.contains("dividend rate"), "{}", error);
+
+    config.dividend_rate.clear();
+    config.volatility = vec![0.25];
+    let error = market.check_config(&config).unwrap_err().to_string();
+    assert!(error.contains("volatility"), "{}", error);
+}
+
+#[test]
+fn test_malformed_lines_give_line_number() {
+    let error = parse_error(&SMALL_MARKET.replace("101.000000 89.000000", "101.000000 abc"));
+    assert!(error.contains("line 9"), "{}", error);
+
+    let error = parse_error(&SMALL_MARKET.replace("101.000000 89.000000", "101.000000"));
+    assert!(error.contains("line 9: 1 prices"), "{}", error);
+
+    let error = parse_error(&SMALL_MARKET.replace("# correlation: 0.500000", "# correlation: x"));
+    assert!(error.contains("line 6"), "{}", error);
+
+    let error = parse_error(&format!("{}# spot: 100.0 90.0\n", SMALL_MARKET));
+    assert!(error.contains("line 11"), "{}", error);
+}
+
+#[test]
+fn test_counts_checked_against_headers() {
+    let error = parse_error(&SMALL_MARKET.replace("102.500000 91.250000\n", ""));
+    assert!(error.contains("2 price rows"), "{}", error);
+
+    let error = parse_error(&SMALL_MARKET.replace("# spot: 100.000000 90.000000", "# spot: 100.0"));
+    assert!(error.contains("\"spot\" has 1 values"), "{}", error);
+
+    let error = parse_error(&SMALL_MARKET.replace("# volatility: 0.200000 0.300000\n", ""));
+    assert!(error.contains("missing header \"volatility\""), "{}", error);
+
+    let error = parse_error(&SMALL_MARKET.replace("# model size: 2", "# model size: 1.5"));
+    assert!(error.contains("model size"), "{}", error);
+}
+
+#[test]
+fn test_simulated_market_round_trip() {
+    let json = read_json("data/basket/basket_5d/basket_5d.json");
+    let config = PricingConfig::from_json(&json).unwrap();
+    let model = BlackScholesModel::from_config(&config).unwrap();
+    let trend = ndarray::Array1::from(config.trend.clone());
+    let prices = model
+        .simulate_market(&trend, config.hedging_dates_number, &mut rand::rng())
+        .unwrap();
+    let market = MarketData::from_model(&model, prices);
+
+    let path = std::env::temp_dir().join(format!("pcpd_market_{}.txt", std::process::id()));
+    let path = path.to_str().unwrap();
+    market.write_file(path).unwrap();
+    let read = MarketData::from_file(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    read.check_config(&config).unwrap();
+    assert_eq!(read.prices.dim(), market.prices.dim());
+    assert!(
+        read.prices
+            .iter()
+            .zip(&market.prices)
+            .all(|(x, y)| (x - y).abs() <= 5e-7)
+    );
+}
@@ -0,0 +1,181 @@
+use pcpd::config::PricingConfig;
+use pcpd::error::PcpdError;
+use pcpd::mc::pricer::MonteCarlo;
+use pcpd::mc::rng::Xoshiro256PlusPlus;
+use pcpd::model::analytic::{AnalyticVanilla, VanillaKind};
+use pcpd::model::dynamics::Model;
+use pcpd::model::merton::MertonModel;
+use pcpd::options::call::CallOption;
+use pcpd::options::factory::option_from_config;
+use rand::SeedableRng;
+use serde_json::{Value, json};
+
+mod common;
+use common::read_json;
+
+// sauts baissiers fréquents : lambda = 1, m = -0.1, delta = 0.15
+fn merton_json(path: &str) -> Value {
+    let mut json = read_json(path);
+    json["model type"] = json!("merton");
+    json["jump intensity"] = json!([1.0]);
+    json["jump mean"] = json!([-0.1]);
+    json["jump volatility"] = json!([0.15]);
+    json
+}
+
+fn vanilla(kind: VanillaKind) -> AnalyticVanilla {
+    AnalyticVanilla::new(kind, 100.0, 100.0, 1.0, 0.05, 0.02, 0.2)
+}
+
+#[test]
+fn test_merton_series_limits() {
+    let call = vanilla(VanillaKind::Call);
+
+    // sans sauts, ou avec des sauts de taille 1, on retrouve Black-Scholes
+    assert!((call.merton_price(0.0, -0.1, 0.15) - call.price()).abs() < 1e-12);
+    assert!((call.merton_price(2.0, 0.0, 0.0) - call.price()).abs() < 1e-12);
+
+    // les sauts ajoutent de la variance : le prix augmente
+    assert!(call.merton_price(1.0, -0.1, 0.15) > call.price() + 0.5);
+}
+
+#[test]
+fn test_merton_series_put_call_parity() {
+    let (lambda, m, delta) = (1.0, -0.1, 0.15);
+    let call = vanilla(VanillaKind::Call).merton_price(lambda, m, delta);
+    let put = vanilla(VanillaKind::Put).merton_price(lambda, m, delta);
+
+    let forward = 100.0 * (-0.02f64).exp() - 100.0 * (-0.05f64).exp();
+    assert!((call - put - forward).abs() < 1e-10);
+}
+
+#[test]
+fn test_monte_carlo_call_matches_merton_series() {
+    for fixings in [1, 4] {
+        let mut json = merton_json("data/call/call.json");
+        json["fixing dates number"] = json!(fixings);
+        json["dividend rate"] = json!([0.02]);
+        let config = PricingConfig::from_json(&json).unwrap();
+        let model = MertonModel::from_config(&config).unwrap();
+        let pricer = MonteCarlo::new(model, Box::new(CallOption::new(100.0)), 100000, 0.1);
+
+        let result = pricer
+            .price(&mut Xoshiro256PlusPlus::seed_from_u64(fixings as u64))
+            .unwrap();
+
+        let analytic = AnalyticVanilla::from_config(&config).unwrap();
+        let expected = analytic.merton_price(1.0, -0.1, 0.15);
+        assert!(
+            (result.price - expected).abs() < 5.0 * result.price_std_dev,
+            "{} fixings: {} +- {} vs {}",
+            fixings,
+            result.price,
+            result.price_std_dev,
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_discounted_spots_are_martingales() {
+    for common_jumps in [false, true] {
+        let mut json = merton_json("data/basket/basket_5d/basket_5d.json");
+        json["common jumps"] = json!(common_jumps);
+        json["fixing dates number"] = json!(3);
+        let model = MertonModel::from_json(&json).unwrap();
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(3);
+
+        let m = 40000;
+        let mut sum = ndarray::Array1::<f64>::zeros(5);
+        let mut sum_sq = ndarray::Array1::<f64>::zeros(5);
+        for _ in 0..m {
+            let last = model.asset(&mut rng).unwrap().row(3).to_owned();
+            sum_sq += &(&last * &last);
+            sum += &last;
+        }
+
+        let expected = 100.0 * (model.interest_rate * model.maturity).exp();
+        for j in 0..5 {
+            let mean = sum[j] / m as f64;
+            let std_dev = ((sum_sq[j] / m as f64 - mean * mean) / m as f64).sqrt();
+            assert!(
+                (mean - expected).abs() < 5.0 * std_dev,
+                "{} vs {}",
+                mean,
+                expected
+            );
+        }
+    }
+}
+
+#[test]
+fn test_common_jumps_are_simultaneous() {
+    let mut json = merton_json("data/basket/basket_2d/basket_2d.json");
+    json["volatility"] = json!([0.0]);
+    json["jump volatility"] = json!([0.0]);
+    json["jump intensity"] = json!([3.0]);
+    json["common jumps"] = json!(true);
+    let model = MertonModel::from_json(&json).unwrap();
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(4);
+
+    // sans diffusion ni dispersion des sauts, les deux actifs restent égaux
+    let mut jumped = false;
+    for _ in 0..100 {
+        let path = model.asset(&mut rng).unwrap();
+        let last = path.row(path.nrows() - 1);
+        assert!((last[0] - last[1]).abs() < 1e-9);
+        jumped |= last[0] < 100.0;
+    }
+    assert!(jumped);
+}
+
+#[test]
+fn test_existing_payoffs_under_merton() {
+    for path in ["data/asian/asian.json", "data/perf/perf.json"] {
+        let config = PricingConfig::from_json(&merton_json(path)).unwrap();
+        let option = option_from_config(&config).unwrap();
+        let mut pricer = MonteCarlo::from_config(&config, option).unwrap();
+        pricer.sample_number = 2000;
+
+        let result = pricer
+            .price_and_delta(&mut Xoshiro256PlusPlus::seed_from_u64(5))
+            .unwrap();
+        assert!(result.price > 0.0 && result.price.is_finite(), "{}", path);
+
+        // 2 gaussiennes et 1 poisson par actif et par date : trop pour la suite de Sobol
+        let error = pricer
+            .price_and_delta_rqmc(4, &mut Xoshiro256PlusPlus::seed_from_u64(6))
+            .unwrap_err();
+        assert!(matches!(error, PcpdError::Config(_)), "{}", path);
+    }
+}
+
+#[test]
+fn test_rqmc_under_merton() {
+    let config = PricingConfig::from_json(&merton_json("data/call/call.json")).unwrap();
+    let option = option_from_config(&config).unwrap();
+    let mut pricer = MonteCarlo::from_config(&config, option).unwrap();
+    pricer.sample_number = 2000;
+    assert_eq!(pricer.model.gaussians_number(0.0), 3);
+
+    let rqmc = pricer
+        .price_and_delta_rqmc(4, &mut Xoshiro256PlusPlus::seed_from_u64(6))
+        .unwrap();
+    assert!(rqmc.price > 0.0 && rqmc.price.is_finite());
+}
+
+#[test]
+fn test_merton_config_errors() {
+    let mut json = merton_json("data/call/call.json");
+    json.as_object_mut().unwrap().remove("jump mean");
+    let error = MertonModel::from_json(&json).err().unwrap().to_string();
+    assert!(error.contains("jump mean"), "{}", error);
+
+    let mut json = merton_json("data/basket/basket_2d/basket_2d.json");
+    json["jump intensity"] = json!([1.0, 2.0]);
+    json["common jumps"] = json!(true);
+    assert!(matches!(
+        MertonModel::from_json(&json),
+        Err(PcpdError::Model(_))
+    ));
+}
@@ -0,0 +1,198 @@
+use ndarray::{Array2, ArrayView1, array};
+use pcpd::config::PricingConfig;
+use pcpd::error::PcpdError;
+use pcpd::mc::control::GeometricControl;
+use pcpd::mc::greeks::GreeksMethod;
+use pcpd::mc::hedging::Hedger;
+use pcpd::mc::pricer::MonteCarlo;
+use pcpd::mc::rng::{RandomSource, Xoshiro256PlusPlus};
+use pcpd::model::black_scholes::BlackScholesModel;
+use pcpd::model::dynamics::Model;
+use pcpd::model::factory::model_from_config;
+use pcpd::options::call::CallOption;
+use rand::SeedableRng;
+
+mod common;
+use common::read_json;
+
+// un actif sans volatilité : S_t = S_0 e^{rt}, défini hors du crate
+struct DeterministicModel {
+    spots: ndarray::Array1<f64>,
+    dividend_rate: ndarray::Array1<f64>,
+}
+
+impl Model for DeterministicModel {
+    fn model_size(&self) -> usize {
+        1
+    }
+
+    fn maturity(&self) -> f64 {
+        1.0
+    }
+
+    fn fixings_dates_number(&self) -> usize {
+        4
+    }
+
+    fn interest_rate(&self) -> f64 {
+        0.05
+    }
+
+    fn dividend_rate(&self) -> ArrayView1<'_, f64> {
+        self.dividend_rate.view()
+    }
+
+    fn spots(&self) -> ArrayView1<'_, f64> {
+        self.spots.view()
+    }
+
+    fn asset_from(
+        &self,
+        past: &Array2<f64>,
+        t: f64,
+        _rng: &mut dyn RandomSource,
+    ) -> Result<Array2<f64>, PcpdError> {
+        let last_index = self.last_fixing_index(t);
+        let spot_t = past[[past.nrows() - 1, 0]];
+
+        Ok(Array2::from_shape_fn((5, 1), |(i, _)| {
+            if i <= last_index {
+                past[[i, 0]]
+            } else {
+                spot_t * (0.05 * (i as f64 * self.time_step() - t)).exp()
+            }
+        }))
+    }
+}
+
+fn deterministic() -> DeterministicModel {
+    DeterministicModel {
+        spots: array![100.0],
+        dividend_rate: array![0.0],
+    }
+}
+
+#[test]
+fn test_pricer_accepts_any_model() {
+    let pricer = MonteCarlo::new(deterministic(), Box::new(CallOption::new(90.0)), 100, 0.01);
+
+    let result = pricer
+        .price_and_delta(&mut Xoshiro256PlusPlus::seed_from_u64(1))
+        .unwrap();
+
+    // (S_0 e^{rT} - K) e^{-rT} = S_0 - K e^{-rT}, de delta 1
+    assert!((result.price - (100.0 - 90.0 * (-0.05f64).exp())).abs() < 1e-10);
+    assert_eq!(result.price_std_dev, 0.0);
+    assert!((result.delta[0] - 1.0).abs() < 1e-10);
+}
+
+#[test]
+fn test_hedger_accepts_any_model() {
+    let pricer = MonteCarlo::new(deterministic(), Box::new(CallOption::new(90.0)), 10, 0.01);
+    let hedger = Hedger::new(pricer, 4).unwrap();
+    let market = Array2::from_shape_fn((5, 1), |(i, _)| 100.0 * (0.05 * i as f64 / 4.0).exp());
+
+    let result = hedger
+        .hedge(&market, &mut Xoshiro256PlusPlus::seed_from_u64(2))
+        .unwrap();
+
+    assert!(result.final_pnl.abs() < 1e-9, "{}", result.final_pnl);
+}
+
+#[test]
+fn test_black_scholes_only_methods() {
+    let mut pricer = MonteCarlo::new(deterministic(), Box::new(CallOption::new(90.0)), 100, 0.01);
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(3);
+
+    let error = pricer.greeks(GreeksMethod::Pathwise, &mut rng).unwrap_err();
+    assert!(matches!(error, PcpdError::Model(_)));
+
+    pricer.control = Some(Box::new(
+        GeometricControl::new(90.0, &array![1.0], false).unwrap(),
+    ));
+    assert!(matches!(pricer.price(&mut rng), Err(PcpdError::Model(_))));
+}
+
+#[test]
+fn test_registry_matches_concrete_model() {
+    let json = read_json("data/asian/asian.json");
+    let config = PricingConfig::from_json(&json).unwrap();
+    let option = || pcpd::options::factory::option_from_config(&config).unwrap();
+
+    let concrete = MonteCarlo::new(
+        BlackScholesModel::from_config(&config).unwrap(),
+        option(),
+        2000,
+        0.1,
+    );
+    let registered = MonteCarlo::new(model_from_config(&config).unwrap(), option(), 2000, 0.1);
+
+    let first = concrete
+        .price_and_delta(&mut Xoshiro256PlusPlus::seed_from_u64(4))
+        .unwrap();
+    let second = registered
+        .price_and_delta(&mut Xoshiro256PlusPlus::seed_from_u64(4))
+        .unwrap();
+
+    assert_eq!(first.price, second.price);
+    assert_eq!(first.delta, second.delta);
+}
+
+// modèle qui surcharge des méthodes fournies par le trait : sans actualisation
+struct UndiscountedModel(DeterministicModel);
+
+impl Model for UndiscountedModel {
+    fn model_size(&self) -> usize {
+        self.0.model_size()
+    }
+
+    fn maturity(&self) -> f64 {
+        self.0.maturity()
+    }
+
+    fn fixings_dates_number(&self) -> usize {
+        self.0.fixings_dates_number()
+    }
+
+    fn interest_rate(&self) -> f64 {
+        self.0.interest_rate()
+    }
+
+    fn dividend_rate(&self) -> ArrayView1<'_, f64> {
+        self.0.dividend_rate()
+    }
+
+    fn spots(&self) -> ArrayView1<'_, f64> {
+        self.0.spots()
+    }
+
+    fn asset_from(
+        &self,
+        past: &Array2<f64>,
+        t: f64,
+        rng: &mut dyn RandomSource,
+    ) -> Result<Array2<f64>, PcpdError> {
+        self.0.asset_from(past, t, rng)
+    }
+
+    fn discount(&self, _t: f64) -> f64 {
+        1.0
+    }
+
+    fn last_fixing_index(&self, _t: f64) -> usize {
+        0
+    }
+}
+
+#[test]
+fn test_boxed_model_keeps_overrides() {
+    let model: Box<dyn Model> = Box::new(UndiscountedModel(deterministic()));
+    assert_eq!(model.discount(0.0), 1.0);
+    assert_eq!(model.last_fixing_index(0.9), 0);
+
+    let pricer = MonteCarlo::new(model, Box::new(CallOption::new(90.0)), 10, 0.01);
+    let result = pricer
+        .price(&mut Xoshiro256PlusPlus::seed_from_u64(2))
+        .unwrap();
+    assert!((result.price - (100.0 * 0.05f64.exp() - 90.0)).abs() < 1e-10);
+}
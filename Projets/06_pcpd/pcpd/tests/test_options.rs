@@ -1,7 +1,12 @@
 use ndarray::Array2;
 use ndarray::array;
+use pcpd::error::PcpdError;
+use pcpd::options::asian::AsianOption;
+use pcpd::options::basket::BasketOption;
 use pcpd::options::call::CallOption;
+use pcpd::options::digital::DigitalOption;
 use pcpd::options::option::Option;
+use pcpd::options::perf::PerformanceOption;
 use pcpd::options::put::PutOption;
 
 #[test]
@@ -12,7 +17,7 @@ fn test_payoff_of_call_1() {
 
     let call = CallOption::new(strike);
 
-    let payoff_call = call.payoff(&path);
+    let payoff_call = call.payoff(&path).unwrap();
     assert_eq!(payoff_call, 10.)
 }
 
@@ -24,7 +29,7 @@ fn test_payoff_of_call_2() {
 
     let call = CallOption::new(strike);
 
-    let payoff_call = call.payoff(&path);
+    let payoff_call = call.payoff(&path).unwrap();
     assert_eq!(payoff_call, 0.)
 }
 
@@ -36,7 +41,7 @@ fn test_payoff_of_put_1() {
 
     let put = PutOption::new(strike);
 
-    let payoff_call = put.payoff(&path);
+    let payoff_call = put.payoff(&path).unwrap();
     assert_eq!(payoff_call, 0.)
 }
 
@@ -48,6 +53,104 @@ fn test_payoff_of_put_2() {
 
     let put = PutOption::new(strike);
 
-    let payoff_call = put.payoff(&path);
+    let payoff_call = put.payoff(&path).unwrap();
     assert_eq!(payoff_call, 10.)
 }
+
+#[test]
+fn test_payoff_of_performance_1() {
+    let path: Array2<f64> = array![[100.0, 100.0], [110.0, 90.0], [120.0, 110.0]];
+
+    let perf = PerformanceOption::new(array![0.5, 0.5]);
+
+    // panier : 100, 100, 115 => 1 + 0 + 0.15
+    let payoff_perf = perf.payoff(&path).unwrap();
+    assert!((payoff_perf - 1.15).abs() < 1e-12)
+}
+
+#[test]
+fn test_payoff_of_performance_2() {
+    let path: Array2<f64> = array![[100.0], [80.0], [100.0], [90.0]];
+
+    let perf = PerformanceOption::new(array![1.0]);
+
+    // performances : -20%, +25%, -10%
+    let payoff_perf = perf.payoff(&path).unwrap();
+    assert!((payoff_perf - 1.25).abs() < 1e-12)
+}
+
+#[test]
+fn test_payoff_of_basket_with_wrong_size() {
+    let path: Array2<f64> = array![[90.0, 100.0], [100.0, 110.0]];
+
+    let basket = BasketOption::new(100.0);
+
+    let payoff_basket = basket.payoff(&path);
+    assert!(matches!(payoff_basket, Err(PcpdError::Payoff(_))))
+}
+
+#[test]
+fn test_payoff_of_call_with_empty_path() {
+    let path: Array2<f64> = Array2::zeros((0, 1));
+
+    let call = CallOption::new(100.0);
+
+    let payoff_call = call.payoff(&path);
+    assert!(matches!(payoff_call, Err(PcpdError::Payoff(_))))
+}
+
+// gradient comparé aux différences finies du payoff, coordonnée par coordonnée
+fn assert_gradient_matches_payoff(option: &dyn Option, path: &Array2<f64>) {
+    let gradient = option.gradient(path).unwrap();
+    let h = 1e-6;
+
+    for ((i, j), &g) in gradient.indexed_iter() {
+        let mut up = path.clone();
+        up[[i, j]] += h;
+        let mut down = path.clone();
+        down[[i, j]] -= h;
+
+        let fd = (option.payoff(&up).unwrap() - option.payoff(&down).unwrap()) / (2.0 * h);
+        assert!((g - fd).abs() < 1e-6, "({}, {}): {} vs {}", i, j, g, fd);
+    }
+}
+
+#[test]
+fn test_gradient_of_performance() {
+    let path: Array2<f64> = array![[100.0, 100.0], [110.0, 90.0], [120.0, 110.0]];
+
+    assert_gradient_matches_payoff(&PerformanceOption::new(array![0.3, 0.7]), &path);
+}
+
+#[test]
+fn test_gradient_of_asian_and_basket() {
+    let path: Array2<f64> = array![[100.0, 100.0], [110.0, 90.0], [120.0, 110.0]];
+    let coefficients = array![0.5, 0.5];
+
+    let mut asian = AsianOption::new(100.0);
+    asian.payoff_coeffcients = coefficients.clone();
+    asian.model_size = 2;
+    assert_gradient_matches_payoff(&asian, &path);
+
+    let mut basket = BasketOption::new(100.0);
+    basket.payoff_coeffcients = coefficients;
+    basket.model_size = 2;
+    assert_gradient_matches_payoff(&basket, &path);
+}
+
+#[test]
+fn test_gradient_of_call_and_put() {
+    let path: Array2<f64> = array![[100.0], [95.0]];
+
+    assert_gradient_matches_payoff(&CallOption::new(90.0), &path);
+    assert_gradient_matches_payoff(&PutOption::new(100.0), &path);
+}
+
+#[test]
+fn test_digital_has_no_gradient() {
+    let path: Array2<f64> = array![[100.0], [110.0]];
+    let digital = DigitalOption::new(100.0);
+
+    assert_eq!(digital.payoff(&path).unwrap(), 1.0);
+    assert!(matches!(digital.gradient(&path), Err(PcpdError::Payoff(_))));
+}
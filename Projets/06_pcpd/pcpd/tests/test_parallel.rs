@@ -0,0 +1,67 @@
+use pcpd::mc::rng::{RandomSource, SplittableSource, Xoshiro256PlusPlus};
+use rand::SeedableRng;
+
+mod common;
+use common::pricer;
+
+fn with_threads<T: Send>(threads: usize, f: impl FnOnce() -> T + Send) -> T {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .unwrap()
+        .install(f)
+}
+
+#[test]
+fn test_split_is_deterministic() {
+    let mut rng_1 = Xoshiro256PlusPlus::seed_from_u64(11);
+    let mut rng_2 = Xoshiro256PlusPlus::seed_from_u64(11);
+
+    let streams_1 = rng_1.split(4, 1000);
+    let streams_2 = rng_2.split(4, 1000);
+
+    assert_eq!(streams_1, streams_2);
+    assert_eq!(rng_1, rng_2);
+}
+
+#[test]
+fn test_split_streams_are_distinct() {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(11);
+
+    let mut streams = rng.split(3, 1000);
+    let g: Vec<Vec<f64>> = streams.iter_mut().map(|s| s.normal_vec(10)).collect();
+
+    assert_ne!(g[0], g[1]);
+    assert_ne!(g[1], g[2]);
+
+    // un second découpage donne de nouveaux flux
+    let mut next = rng.split(1, 1000);
+    assert_ne!(next[0].normal_vec(10), g[0]);
+}
+
+#[test]
+fn test_price_does_not_depend_on_thread_count() {
+    // 2500 tirages : deux paquets complets et un paquet partiel
+    let pricer = pricer("data/basket/basket_5d_1/basket_5d_1.json", 2500);
+
+    let results: Vec<_> = [1, 2, 4]
+        .into_iter()
+        .map(|threads| {
+            with_threads(threads, || {
+                pricer
+                    .price_and_delta(&mut Xoshiro256PlusPlus::seed_from_u64(99))
+                    .unwrap()
+            })
+        })
+        .collect();
+
+    for result in &results[1..] {
+        assert_eq!(result.price.to_bits(), results[0].price.to_bits());
+        assert_eq!(
+            result.price_std_dev.to_bits(),
+            results[0].price_std_dev.to_bits()
+        );
+        assert_eq!(result.delta, results[0].delta);
+        assert_eq!(result.delta_std_dev, results[0].delta_std_dev);
+    }
+}
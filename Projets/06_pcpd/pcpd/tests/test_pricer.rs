@@ -0,0 +1,228 @@
+use ndarray::{Axis, array};
+use pcpd::mc::pricer::{DeltaResult, MonteCarlo, PriceResult};
+use pcpd::mc::rng::Xoshiro256PlusPlus;
+use pcpd::model::black_scholes::BlackScholesModel;
+use pcpd::options::asian::AsianOption;
+use pcpd::options::basket::BasketOption;
+use pcpd::options::call::CallOption;
+use pcpd::options::option::Option;
+use pcpd::options::perf::PerformanceOption;
+use rand::SeedableRng;
+
+mod common;
+use common::read_json;
+
+// le prix doit être à moins de 5 écarts-types (des deux estimateurs) du prix attendu
+fn assert_close_to_expected(result: &PriceResult, expected_path: &str) {
+    let expected = read_json(expected_path);
+    let expected_price = expected["price"].as_f64().unwrap();
+    let expected_std_dev = expected["priceStdDev"].as_f64().unwrap();
+
+    let tolerance = 5.0 * (result.price_std_dev.powi(2) + expected_std_dev.powi(2)).sqrt();
+
+    assert!(
+        (result.price - expected_price).abs() < tolerance,
+        "price {} too far from expected {} (tolerance {})",
+        result.price,
+        expected_price,
+        tolerance
+    );
+}
+
+#[test]
+fn test_price_of_call() {
+    let json = read_json("data/call/call.json");
+    let model = BlackScholesModel::from_json(&json).unwrap();
+    let option = CallOption::new(json["strike"].as_f64().unwrap());
+    let pricer = MonteCarlo::new(model, Box::new(option), 50000, 0.1);
+
+    let result = pricer
+        .price(&mut Xoshiro256PlusPlus::seed_from_u64(101))
+        .unwrap();
+
+    assert_close_to_expected(&result, "data/call/call_expected_price.json");
+    assert!(result.confidence_interval.0 < result.price);
+    assert!(result.price < result.confidence_interval.1);
+}
+
+#[test]
+fn test_price_of_asian() {
+    let json = read_json("data/asian/asian.json");
+    let model = BlackScholesModel::from_json(&json).unwrap();
+    let option = AsianOption::from_json(&json).unwrap();
+    let pricer = MonteCarlo::new(model, Box::new(option), 50000, 0.1);
+
+    let result = pricer
+        .price(&mut Xoshiro256PlusPlus::seed_from_u64(102))
+        .unwrap();
+
+    assert_close_to_expected(&result, "data/asian/asian_expected_price.json");
+}
+
+#[test]
+fn test_price_of_basket_5d() {
+    let json = read_json("data/basket/basket_5d/basket_5d.json");
+    let model = BlackScholesModel::from_json(&json).unwrap();
+    let option = BasketOption::from_json(&json).unwrap();
+    let pricer = MonteCarlo::new(model, Box::new(option), 50000, 0.1);
+
+    let result = pricer
+        .price(&mut Xoshiro256PlusPlus::seed_from_u64(103))
+        .unwrap();
+
+    assert_close_to_expected(
+        &result,
+        "data/basket/basket_5d/basket_5d_expected_price.json",
+    );
+}
+
+#[test]
+fn test_price_of_performance() {
+    let json = read_json("data/perf/perf.json");
+    let model = BlackScholesModel::from_json(&json).unwrap();
+    let option = PerformanceOption::from_json(&json).unwrap();
+    let pricer = MonteCarlo::new(model, Box::new(option), 50000, 0.1);
+
+    let result = pricer
+        .price(&mut Xoshiro256PlusPlus::seed_from_u64(104))
+        .unwrap();
+
+    assert_close_to_expected(&result, "data/perf/perf_expected_price.json");
+}
+
+#[test]
+fn test_antithetic_price_of_call() {
+    let json = read_json("data/call/call.json");
+    let model = BlackScholesModel::from_json(&json).unwrap();
+    let option = CallOption::new(json["strike"].as_f64().unwrap());
+    let mut pricer = MonteCarlo::new(model, Box::new(option), 50000, 0.1);
+    pricer.antithetic = true;
+
+    let result = pricer
+        .price(&mut Xoshiro256PlusPlus::seed_from_u64(105))
+        .unwrap();
+
+    assert_close_to_expected(&result, "data/call/call_expected_price.json");
+    // même nombre de trajectoires, mais le payoff est monotone en g : la variance diminue
+    let expected = read_json("data/call/call_expected_price.json");
+    assert!(result.price_std_dev < expected["priceStdDev"].as_f64().unwrap());
+}
+
+#[test]
+fn test_antithetic_delta_of_asian() {
+    let json = read_json("data/asian/asian.json");
+    let model = BlackScholesModel::from_json(&json).unwrap();
+    let option = AsianOption::from_json(&json).unwrap();
+    let mut pricer = MonteCarlo::new(model, Box::new(option), 50000, 0.1);
+    pricer.antithetic = true;
+
+    let result = pricer
+        .delta(&mut Xoshiro256PlusPlus::seed_from_u64(106))
+        .unwrap();
+
+    assert_delta_close_to_expected(&result, "data/asian/asian_expected_price.json");
+}
+
+#[test]
+fn test_price_at_maturity_is_discounted_payoff() {
+    let json = read_json("data/asian/asian.json");
+    let model = BlackScholesModel::from_json(&json).unwrap();
+    let maturity = model.maturity;
+    let past = model.asset(&mut rand::rng()).unwrap();
+
+    let option = AsianOption::from_json(&json).unwrap();
+    let expected_payoff = option.payoff(&past).unwrap();
+    let pricer = MonteCarlo::new(model, Box::new(option), 100, 0.1);
+
+    let result = pricer
+        .price_at(&past, maturity, &mut Xoshiro256PlusPlus::seed_from_u64(107))
+        .unwrap();
+
+    assert!((result.price - expected_payoff).abs() < 1e-12);
+    assert_eq!(result.price_std_dev, 0.0);
+}
+
+#[test]
+fn test_price_at_is_close_to_price_at_start() {
+    let json = read_json("data/asian/asian.json");
+    let model = BlackScholesModel::from_json(&json).unwrap();
+    let past = model.spots.clone().insert_axis(Axis(0));
+    let option = AsianOption::from_json(&json).unwrap();
+    let pricer = MonteCarlo::new(model, Box::new(option), 50000, 0.1);
+
+    let result = pricer
+        .price_at(&past, 0.0, &mut Xoshiro256PlusPlus::seed_from_u64(108))
+        .unwrap();
+
+    assert_close_to_expected(&result, "data/asian/asian_expected_price.json");
+}
+
+// chaque delta doit être à moins de 5 écarts-types du delta attendu
+fn assert_delta_close_to_expected(result: &DeltaResult, expected_path: &str) {
+    let expected = read_json(expected_path);
+    let expected_delta = expected["delta"].as_array().unwrap();
+    let expected_std_dev = expected["deltaStdDev"].as_array().unwrap();
+
+    assert_eq!(result.delta.len(), expected_delta.len());
+
+    for j in 0..expected_delta.len() {
+        let expected_delta_j = expected_delta[j].as_f64().unwrap();
+        let expected_std_dev_j = expected_std_dev[j].as_f64().unwrap();
+        let tolerance = 5.0 * (result.delta_std_dev[j].powi(2) + expected_std_dev_j.powi(2)).sqrt();
+
+        assert!(
+            (result.delta[j] - expected_delta_j).abs() < tolerance,
+            "delta {} of asset {} too far from expected {} (tolerance {})",
+            result.delta[j],
+            j,
+            expected_delta_j,
+            tolerance
+        );
+    }
+}
+
+#[test]
+fn test_delta_of_call() {
+    let json = read_json("data/call/call.json");
+    let model = BlackScholesModel::from_json(&json).unwrap();
+    let option = CallOption::new(json["strike"].as_f64().unwrap());
+    let pricer = MonteCarlo::new(model, Box::new(option), 50000, 0.1);
+
+    let result = pricer
+        .delta(&mut Xoshiro256PlusPlus::seed_from_u64(109))
+        .unwrap();
+
+    assert_delta_close_to_expected(&result, "data/call/call_expected_price.json");
+}
+
+#[test]
+fn test_delta_of_asian() {
+    let json = read_json("data/asian/asian.json");
+    let model = BlackScholesModel::from_json(&json).unwrap();
+    let option = AsianOption::from_json(&json).unwrap();
+    let pricer = MonteCarlo::new(model, Box::new(option), 50000, 0.1);
+
+    let result = pricer
+        .delta(&mut Xoshiro256PlusPlus::seed_from_u64(110))
+        .unwrap();
+
+    assert_delta_close_to_expected(&result, "data/asian/asian_expected_price.json");
+}
+
+#[test]
+fn test_delta_at_maturity_of_call() {
+    let json = read_json("data/call/call.json");
+    let model = BlackScholesModel::from_json(&json).unwrap();
+    let maturity = model.maturity;
+    let option = CallOption::new(json["strike"].as_f64().unwrap());
+    let pricer = MonteCarlo::new(model, Box::new(option), 100, 0.1);
+
+    // à maturité, le delta d'un call dans la monnaie vaut 1
+    let past = array![[100.0], [120.0]];
+    let result = pricer
+        .delta_at(&past, maturity, &mut Xoshiro256PlusPlus::seed_from_u64(111))
+        .unwrap();
+
+    assert!((result.delta[0] - 1.0).abs() < 1e-12);
+    assert!(result.delta_std_dev[0] < 1e-6);
+}
@@ -0,0 +1,111 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use pcpd::config::PricingConfig;
+use pcpd::mc::pricer::MonteCarlo;
+use pcpd::mc::rng::Xoshiro256PlusPlus;
+use pcpd::options::factory::option_from_config;
+use rand::SeedableRng;
+
+mod common;
+use common::read_json;
+
+// nombre d'écarts-types tolérés entre le résultat et la valeur attendue
+const TOLERANCE: f64 = 5.0;
+
+// tous les fichiers de configuration de data/ accompagnés d'un *_expected_price.json
+fn data_cases() -> Vec<PathBuf> {
+    let mut cases = Vec::new();
+    let mut dirs = vec![Path::new(env!("CARGO_MANIFEST_DIR")).join("data")];
+
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if expected_file(&path, "price").exists() {
+                cases.push(path);
+            }
+        }
+    }
+
+    cases.sort();
+    cases
+}
+
+// data/call/call.json => data/call/call_expected_<kind>.json
+fn expected_file(config: &Path, kind: &str) -> PathBuf {
+    let stem = config.file_stem().unwrap().to_str().unwrap();
+    config.with_file_name(format!("{}_expected_{}.json", stem, kind))
+}
+
+fn check(case: &Path, name: &str, value: f64, std_dev: f64, expected: f64, expected_std_dev: f64) {
+    let tolerance = TOLERANCE * (std_dev.powi(2) + expected_std_dev.powi(2)).sqrt();
+
+    assert!(
+        (value - expected).abs() < tolerance,
+        "{}: {} = {} too far from expected {} (tolerance {})",
+        case.display(),
+        name,
+        value,
+        expected,
+        tolerance
+    );
+}
+
+#[test]
+fn test_data_cases_are_found() {
+    let cases = data_cases();
+
+    assert_eq!(cases.len(), 7);
+}
+
+#[test]
+fn test_price_and_delta_of_data_cases() {
+    for case in data_cases() {
+        let config = PricingConfig::from_file(case.to_str().unwrap()).unwrap();
+        let option = option_from_config(&config).unwrap();
+        let pricer = MonteCarlo::from_config(&config, option).unwrap();
+
+        let result = pricer
+            .price_and_delta(&mut Xoshiro256PlusPlus::seed_from_u64(7))
+            .unwrap();
+
+        // *_expected_price.json : prix et deltas en t = 0
+        let expected = read_json(expected_file(&case, "price"));
+        check(
+            &case,
+            "price",
+            result.price,
+            result.price_std_dev,
+            expected["price"].as_f64().unwrap(),
+            expected["priceStdDev"].as_f64().unwrap(),
+        );
+
+        let expected_delta = expected["delta"].as_array().unwrap();
+        let expected_delta_std_dev = expected["deltaStdDev"].as_array().unwrap();
+        assert_eq!(result.delta.len(), expected_delta.len());
+
+        for j in 0..expected_delta.len() {
+            check(
+                &case,
+                &format!("delta[{}]", j),
+                result.delta[j],
+                result.delta_std_dev[j],
+                expected_delta[j].as_f64().unwrap(),
+                expected_delta_std_dev[j].as_f64().unwrap(),
+            );
+        }
+
+        // *_expected_hedge.json : prix initial d'un autre tirage
+        let expected = read_json(expected_file(&case, "hedge"));
+        check(
+            &case,
+            "initialPrice",
+            result.price,
+            result.price_std_dev,
+            expected["initialPrice"].as_f64().unwrap(),
+            expected["initialPriceStdDev"].as_f64().unwrap(),
+        );
+    }
+}
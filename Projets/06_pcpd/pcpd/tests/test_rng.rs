@@ -0,0 +1,100 @@
+use pcpd::mc::rng::{AntitheticSource, RandomSource, Xoshiro256PlusPlus};
+use rand::{RngCore, SeedableRng};
+
+mod common;
+use common::pricer;
+
+#[test]
+fn test_xoshiro_reference_values() {
+    // valeurs de référence de l'implémentation C de xoshiro256++ pour l'état [1, 2, 3, 4]
+    let mut seed = [0u8; 32];
+    for (i, x) in [1u64, 2, 3, 4].iter().enumerate() {
+        seed[8 * i..8 * (i + 1)].copy_from_slice(&x.to_le_bytes());
+    }
+    let mut rng = Xoshiro256PlusPlus::from_seed(seed);
+
+    let expected: [u64; 6] = [
+        41943041,
+        58720359,
+        3588806011781223,
+        3591011842654386,
+        9228616714210784205,
+        9973669472204895162,
+    ];
+    for x in expected {
+        assert_eq!(rng.next_u64(), x);
+    }
+}
+
+#[test]
+fn test_same_seed_same_normals() {
+    let mut rng_1 = Xoshiro256PlusPlus::seed_from_u64(2025);
+    let mut rng_2 = Xoshiro256PlusPlus::seed_from_u64(2025);
+    let mut rng_3 = Xoshiro256PlusPlus::seed_from_u64(2026);
+
+    let g_1 = rng_1.normal_vec(100);
+    let g_2 = rng_2.normal_vec(100);
+    let g_3 = rng_3.normal_vec(100);
+
+    assert_eq!(g_1, g_2);
+    assert_ne!(g_1, g_3);
+}
+
+#[test]
+fn test_antithetic_source_alternates_draws_and_opposites() {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(2025);
+    let mut reference = rng.clone();
+    let mut antithetic = AntitheticSource::new(&mut rng);
+
+    let g_1 = antithetic.normal_vec(10);
+    let g_1_bar = antithetic.normal_vec(10);
+    let g_2 = antithetic.normal_vec(10);
+
+    assert_eq!(g_1, reference.normal_vec(10));
+    assert_eq!(g_1_bar, g_1.iter().map(|x| -x).collect::<Vec<f64>>());
+    assert_eq!(g_2, reference.normal_vec(10));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "one normal_vec call")]
+fn test_antithetic_source_rejects_size_change() {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(2025);
+    let mut antithetic = AntitheticSource::new(&mut rng);
+
+    antithetic.normal_vec(10);
+    antithetic.normal_vec(5);
+}
+
+#[test]
+fn test_same_seed_same_price() {
+    let pricer = pricer("data/basket/basket_5d_1/basket_5d_1.json", 2000);
+
+    let first = pricer
+        .price_and_delta(&mut Xoshiro256PlusPlus::seed_from_u64(7))
+        .unwrap();
+    let second = pricer
+        .price_and_delta(&mut Xoshiro256PlusPlus::seed_from_u64(7))
+        .unwrap();
+
+    assert_eq!(first.price.to_bits(), second.price.to_bits());
+    assert_eq!(
+        first.price_std_dev.to_bits(),
+        second.price_std_dev.to_bits()
+    );
+    assert_eq!(first.delta, second.delta);
+}
+
+#[test]
+fn test_different_seeds_different_prices() {
+    let pricer = pricer("data/asian/asian.json", 1000);
+
+    let first = pricer
+        .price(&mut Xoshiro256PlusPlus::seed_from_u64(1))
+        .unwrap();
+    let second = pricer
+        .price(&mut Xoshiro256PlusPlus::seed_from_u64(2))
+        .unwrap();
+
+    assert_ne!(first.price, second.price);
+}
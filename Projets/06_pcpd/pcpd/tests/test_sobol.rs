@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use pcpd::error::PcpdError;
+use pcpd::math::random::normal_inverse_cdf;
+use pcpd::mc::pricer::MonteCarlo;
+use pcpd::mc::rng::{RandomSource, SplittableSource, Xoshiro256PlusPlus};
+use pcpd::mc::sobol::{SOBOL_MAX_DIMENSION, SobolSequence, SobolSource};
+use pcpd::model::black_scholes::{BlackScholesModel, PathConstruction};
+use pcpd::options::asian::AsianOption;
+use pcpd::options::basket::BasketOption;
+use rand::SeedableRng;
+
+mod common;
+use common::read_json;
+
+fn to_unit(x: u32) -> f64 {
+    x as f64 / 2f64.powi(32)
+}
+
+#[test]
+fn test_first_points() {
+    let sequence = SobolSequence::new(2).unwrap();
+
+    let points: Vec<Vec<f64>> = (0..4)
+        .map(|n| sequence.point(n).into_iter().map(to_unit).collect())
+        .collect();
+
+    assert_eq!(
+        points,
+        vec![
+            vec![0.0, 0.0],
+            vec![0.5, 0.5],
+            vec![0.75, 0.25],
+            vec![0.25, 0.75],
+        ]
+    );
+}
+
+#[test]
+fn test_each_dimension_is_stratified() {
+    // les 2^m premiers points ont exactement une coordonnée dans chaque intervalle [k / 2^m, (k + 1) / 2^m[
+    let dimension = SOBOL_MAX_DIMENSION;
+    let sequence = SobolSequence::new(dimension).unwrap();
+    let points: Vec<Vec<u32>> = (0..1024).map(|n| sequence.point(n)).collect();
+
+    for j in 0..dimension {
+        let mut cells: Vec<u32> = points.iter().map(|p| p[j] >> 22).collect();
+        cells.sort();
+        assert_eq!(cells, (0..1024).collect::<Vec<u32>>(), "dimension {}", j);
+    }
+}
+
+#[test]
+fn test_dimension_zero_is_rejected() {
+    assert!(SobolSequence::new(0).is_err());
+}
+
+#[test]
+fn test_dimension_beyond_table_is_rejected() {
+    assert!(SobolSequence::new(SOBOL_MAX_DIMENSION).is_ok());
+    assert!(SobolSequence::new(SOBOL_MAX_DIMENSION + 1).is_err());
+}
+
+#[test]
+fn test_joe_kuo_first_dimensions() {
+    // dimension 3 : x^2 + x + 1, m = (1, 3) ; dimension 4 : x^3 + x + 1, m = (1, 3, 1)
+    let sequence = SobolSequence::new(4).unwrap();
+
+    let points: Vec<Vec<f64>> = (0..4)
+        .map(|n| sequence.point(n).into_iter().map(to_unit).collect())
+        .collect();
+
+    assert_eq!(points[1], vec![0.5, 0.5, 0.5, 0.5]);
+    assert_eq!(points[2], vec![0.75, 0.25, 0.25, 0.25]);
+    assert_eq!(points[3], vec![0.25, 0.75, 0.75, 0.75]);
+}
+
+#[test]
+fn test_split_matches_sequential_points() {
+    let sequence = Arc::new(SobolSequence::new(3).unwrap());
+    let model = BlackScholesModel::from_json(&read_json("data/call/call.json")).unwrap();
+    let source = |sequence| {
+        SobolSource::new(
+            sequence,
+            &model,
+            0.0,
+            &mut Xoshiro256PlusPlus::seed_from_u64(7),
+        )
+        .unwrap()
+    };
+    let mut sequential = source(sequence.clone());
+    let mut parent = source(sequence);
+
+    let expected: Vec<Vec<f64>> = (0..12).map(|_| sequential.normal_vec(3)).collect();
+
+    let mut actual = Vec::new();
+    for mut source in parent.split(3, 4) {
+        for _ in 0..4 {
+            actual.push(source.normal_vec(3));
+        }
+    }
+    assert_eq!(actual, expected);
+
+    // la source découpée reprend après les points distribués
+    assert_eq!(parent.normal_vec(3), sequential.normal_vec(3));
+}
+
+#[test]
+fn test_normal_inverse_cdf() {
+    assert!(normal_inverse_cdf(0.5).abs() < 1e-12);
+    assert!((normal_inverse_cdf(0.975) - 1.959963984540054).abs() < 1e-8);
+    assert!((normal_inverse_cdf(0.01) + 2.326347874040841).abs() < 1e-8);
+    assert!((normal_inverse_cdf(0.3) + normal_inverse_cdf(0.7)).abs() < 1e-12);
+}
+
+#[test]
+fn test_brownian_bridge_paths_have_same_law() {
+    let json = read_json("data/asian/asian.json");
+    let mut model = BlackScholesModel::from_json(&json).unwrap();
+    model.path_construction = PathConstruction::BrownianBridge;
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(1);
+
+    // E[S_{t_i}] = S_0 e^{r t_i} à chaque date de fixing
+    let m = 20000;
+    let n = model.fixings_dates_number;
+    let mut mean = vec![0.0; n + 1];
+    for _ in 0..m {
+        let path = model.asset(&mut rng).unwrap();
+        for i in 0..=n {
+            mean[i] += path[[i, 0]] / m as f64;
+        }
+    }
+
+    for (i, x) in mean.iter().enumerate() {
+        let expected = 100.0 * (model.interest_rate * i as f64 * model.time_step).exp();
+        assert!(
+            (x - expected).abs() < 1.0,
+            "date {}: {} vs {}",
+            i,
+            x,
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_rqmc_price_of_asian() {
+    let json = read_json("data/asian/asian.json");
+    let mut model = BlackScholesModel::from_json(&json).unwrap();
+    model.path_construction = PathConstruction::BrownianBridge;
+    let option = AsianOption::from_json(&json).unwrap();
+    let pricer = MonteCarlo::new(model, Box::new(option), 16 * 1024, 0.1);
+
+    let result = pricer
+        .price_and_delta_rqmc(16, &mut Xoshiro256PlusPlus::seed_from_u64(16))
+        .unwrap();
+
+    let expected = read_json("data/asian/asian_expected_price.json");
+    let expected_price = expected["price"].as_f64().unwrap();
+    let expected_std_dev = expected["priceStdDev"].as_f64().unwrap();
+    let tolerance = 5.0 * (result.price_std_dev.powi(2) + expected_std_dev.powi(2)).sqrt();
+
+    assert!((result.price - expected_price).abs() < tolerance);
+    assert!(result.price_std_dev > 0.0);
+    // 16384 points quasi-aléatoires font mieux que 50000 tirages Monte Carlo
+    assert!(result.price_std_dev < expected_std_dev);
+    for (delta, expected_delta) in result
+        .delta
+        .iter()
+        .zip(expected["delta"].as_array().unwrap())
+    {
+        assert!((delta - expected_delta.as_f64().unwrap()).abs() < 0.01);
+    }
+}
+
+#[test]
+fn test_rqmc_of_large_basket() {
+    // 40 actifs et 2 dates : 80 gaussiennes par trajectoire
+    let mut json = read_json("data/basket/basket_40d/basket_40d.json");
+    json["fixing dates number"] = serde_json::json!(2);
+    let model = BlackScholesModel::from_json(&json).unwrap();
+    let option = BasketOption::from_json(&json).unwrap();
+    let pricer = MonteCarlo::new(model, Box::new(option), 1024, 0.1);
+
+    let result = pricer
+        .price_and_delta_rqmc(4, &mut Xoshiro256PlusPlus::seed_from_u64(3))
+        .unwrap();
+    assert!(result.price > 0.0 && result.price.is_finite());
+    assert_eq!(result.delta.len(), 40);
+
+    // 3 dates : 120 gaussiennes, au-delà de la table
+    json["fixing dates number"] = serde_json::json!(3);
+    let model = BlackScholesModel::from_json(&json).unwrap();
+    let option = BasketOption::from_json(&json).unwrap();
+    let pricer = MonteCarlo::new(model, Box::new(option), 1024, 0.1);
+
+    let error = pricer
+        .price_and_delta_rqmc(4, &mut Xoshiro256PlusPlus::seed_from_u64(3))
+        .unwrap_err();
+    assert!(matches!(error, PcpdError::Config(_)));
+    assert!(error.to_string().contains("120"), "{}", error);
+}
+
+#[test]
+fn test_source_too_small_for_model_is_rejected() {
+    let model = BlackScholesModel::from_json(&read_json("data/asian/asian.json")).unwrap();
+    let sequence = Arc::new(SobolSequence::new(2).unwrap());
+
+    let error = SobolSource::new(
+        sequence,
+        &model,
+        0.0,
+        &mut Xoshiro256PlusPlus::seed_from_u64(1),
+    )
+    .unwrap_err();
+
+    assert!(matches!(error, PcpdError::Model(_)));
+}